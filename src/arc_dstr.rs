@@ -0,0 +1,339 @@
+//! Module for shared, reference-counted nul-terminated UTF-8 strings.
+
+use core::{
+    alloc::Layout,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    ptr::NonNull,
+    slice::from_raw_parts,
+    sync::atomic::{self, AtomicUsize},
+};
+
+use alloc::alloc::{alloc, dealloc, handle_alloc_error};
+
+use crate::{dstr::NulError, DStr, DString};
+
+/// The reference count of static strings, which is never changed.
+const STATIC_COUNT: usize = usize::MAX;
+
+/// The largest reference count before cloning panics, which leaves plenty
+/// of headroom before an overflow.
+const MAX_COUNT: usize = isize::MAX as usize;
+
+/// The header in front of the string data.
+#[repr(C)]
+struct Header {
+    count: AtomicUsize,
+    /// The length of the string, excluding the nul terminator.
+    len: usize,
+}
+
+/// A shared, immutable, reference-counted nul-terminated UTF-8 string.
+///
+/// The reference count, length and string are stored in a single allocation,
+/// while an [`ArcDStr`] itself is a single pointer, as is `Option<ArcDStr>`.
+/// Cloning only increments the reference count, and strings created with
+/// [`arcdstr!`] live in static memory, so they never allocate.
+///
+/// [`arcdstr!`]: crate::arcdstr
+pub struct ArcDStr {
+    ptr: NonNull<Header>,
+}
+
+// SAFETY: An `ArcDStr` behaves the same as an `Arc<DStr>`.
+unsafe impl Send for ArcDStr {}
+
+// SAFETY: An `ArcDStr` behaves the same as an `Arc<DStr>`.
+unsafe impl Sync for ArcDStr {}
+
+impl ArcDStr {
+    /// Creates a new [`ArcDStr`] by copying `string`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocation size would overflow.
+    #[must_use]
+    pub fn new(string: &DStr) -> ArcDStr {
+        let bytes = string.as_bytes_with_nul();
+        let (layout, offset) = ArcDStr::layout(bytes.len());
+
+        // SAFETY: The layout is never zero-sized, as it includes the header.
+        let ptr = unsafe { alloc(layout) };
+
+        let Some(ptr) = NonNull::new(ptr.cast::<Header>()) else {
+            handle_alloc_error(layout)
+        };
+
+        // SAFETY: The allocation fits the header followed by the string.
+        unsafe {
+            ptr.as_ptr().write(Header {
+                count: AtomicUsize::new(1),
+                len: string.len(),
+            });
+
+            ptr.as_ptr()
+                .cast::<u8>()
+                .add(offset)
+                .copy_from_nonoverlapping(bytes.as_ptr(), bytes.len());
+        }
+
+        ArcDStr { ptr }
+    }
+
+    /// Creates a new [`ArcDStr`] pointing to a static string.
+    ///
+    /// This does not allocate, and is usually called through [`arcdstr!`].
+    ///
+    /// [`arcdstr!`]: crate::arcdstr
+    #[inline]
+    #[must_use]
+    pub const fn from_static<const N: usize>(string: &'static StaticArcDStr<N>) -> ArcDStr {
+        // The header is the first field, and the pointer needs to cover the
+        // string as well.
+        let ptr = (string as *const StaticArcDStr<N>).cast::<Header>();
+
+        ArcDStr {
+            // SAFETY: References are never null.
+            ptr: unsafe { NonNull::new_unchecked(ptr.cast_mut()) },
+        }
+    }
+
+    /// Returns the layout of a string of `len` bytes including the nul
+    /// terminator, and the offset of the string within it.
+    #[inline]
+    fn layout(len: usize) -> (Layout, usize) {
+        Layout::array::<u8>(len)
+            .and_then(|bytes| Layout::new::<Header>().extend(bytes))
+            .map(|(layout, offset)| (layout.pad_to_align(), offset))
+            .expect("capacity overflow")
+    }
+
+    #[inline]
+    const fn header(&self) -> &Header {
+        // SAFETY: `ptr` always points to a valid header.
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Returns whether this [`ArcDStr`] points to a static string.
+    #[inline]
+    #[must_use]
+    pub fn is_static(&self) -> bool {
+        self.header().count.load(atomic::Ordering::Relaxed) == STATIC_COUNT
+    }
+
+    /// Returns whether both [`ArcDStr`]s point to the same string.
+    #[inline]
+    #[must_use]
+    pub fn ptr_eq(this: &ArcDStr, other: &ArcDStr) -> bool {
+        this.ptr == other.ptr
+    }
+
+    /// Returns the amount of [`ArcDStr`]s pointing to the same string, or
+    /// `None` if it is static.
+    #[inline]
+    #[must_use]
+    pub fn strong_count(this: &ArcDStr) -> Option<usize> {
+        match this.header().count.load(atomic::Ordering::Relaxed) {
+            STATIC_COUNT => None,
+            count => Some(count),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn as_dstr(&self) -> &DStr {
+        let len = self.header().len + 1;
+
+        // SAFETY: The string always directly follows the header, as both are
+        //         laid out the same as `StaticArcDStr`, and is valid for the
+        //         lifetime of `self`.
+        unsafe {
+            let ptr = self.ptr.as_ptr().add(1).cast::<u8>();
+            DStr::from_bytes_with_nul_unchecked(from_raw_parts(ptr, len))
+        }
+    }
+}
+
+impl Clone for ArcDStr {
+    #[inline]
+    fn clone(&self) -> Self {
+        let count = &self.header().count;
+
+        if count.load(atomic::Ordering::Relaxed) != STATIC_COUNT {
+            // The same as `Arc`, new references can only be created from an
+            // existing one, so no synchronization is needed.
+            let old = count.fetch_add(1, atomic::Ordering::Relaxed);
+
+            if old > MAX_COUNT {
+                count.fetch_sub(1, atomic::Ordering::Relaxed);
+                panic!("reference count overflow");
+            }
+        }
+
+        ArcDStr { ptr: self.ptr }
+    }
+}
+
+impl Drop for ArcDStr {
+    #[inline]
+    fn drop(&mut self) {
+        let count = &self.header().count;
+
+        if count.load(atomic::Ordering::Relaxed) == STATIC_COUNT
+            || count.fetch_sub(1, atomic::Ordering::Release) != 1
+        {
+            return;
+        }
+
+        atomic::fence(atomic::Ordering::Acquire);
+
+        let (layout, _) = ArcDStr::layout(self.header().len + 1);
+
+        // SAFETY: This was the last reference, and the allocation was created
+        //         with the same layout in `ArcDStr::new`.
+        unsafe { dealloc(self.ptr.as_ptr().cast(), layout) };
+    }
+}
+
+impl PartialEq for ArcDStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ArcDStr::ptr_eq(self, other) || self.as_dstr() == other.as_dstr()
+    }
+}
+
+impl Eq for ArcDStr {}
+
+impl PartialOrd for ArcDStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ArcDStr {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_dstr().cmp(other.as_dstr())
+    }
+}
+
+impl Hash for ArcDStr {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_dstr().hash(state)
+    }
+}
+
+impl fmt::Debug for ArcDStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dstr(), f)
+    }
+}
+
+impl Deref for ArcDStr {
+    type Target = DStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_dstr()
+    }
+}
+
+impl From<&DStr> for ArcDStr {
+    #[inline]
+    fn from(value: &DStr) -> Self {
+        ArcDStr::new(value)
+    }
+}
+
+impl From<&DString> for ArcDStr {
+    #[inline]
+    fn from(value: &DString) -> Self {
+        ArcDStr::new(value.as_dstr())
+    }
+}
+
+impl From<DString> for ArcDStr {
+    #[inline]
+    fn from(value: DString) -> Self {
+        ArcDStr::new(value.as_dstr())
+    }
+}
+
+impl From<&ArcDStr> for DString {
+    #[inline]
+    fn from(value: &ArcDStr) -> Self {
+        DString::from(value.as_dstr())
+    }
+}
+
+/// A statically stored string with the same layout as the allocation of an
+/// [`ArcDStr`].
+///
+/// `N` is the length of the string including the nul terminator. This is
+/// usually created through [`arcdstr!`].
+///
+/// [`arcdstr!`]: crate::arcdstr
+#[repr(C)]
+pub struct StaticArcDStr<const N: usize> {
+    header: Header,
+    bytes: [u8; N],
+}
+
+impl<const N: usize> StaticArcDStr<N> {
+    /// Creates a new [`StaticArcDStr`] from `string`, which does not include
+    /// the nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul, or is not `N - 1` bytes long.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn new(string: &str) -> StaticArcDStr<N> {
+        let src = string.as_bytes();
+
+        assert!(src.len() + 1 == N, "string length does not match `N`");
+
+        if let Err(err) = NulError::check_const(src) {
+            err._panic()
+        }
+
+        let mut bytes = [0; N];
+        let mut idx = 0;
+
+        while idx < src.len() {
+            bytes[idx] = src[idx];
+            idx += 1;
+        }
+
+        StaticArcDStr {
+            header: Header {
+                count: AtomicUsize::new(STATIC_COUNT),
+                len: src.len(),
+            },
+            bytes,
+        }
+    }
+}
+
+/// Creates an [`ArcDStr`] from a string literal, stored in static memory.
+///
+/// The literal must not include the nul terminator, and fails to compile if it
+/// contains a nul.
+///
+/// [`ArcDStr`]: crate::ArcDStr
+#[macro_export]
+macro_rules! arcdstr {
+    ($string:expr) => {{
+        const STRING: &str = $string;
+        static STATIC: $crate::arc_dstr::StaticArcDStr<{ STRING.len() + 1 }> =
+            $crate::arc_dstr::StaticArcDStr::new(STRING);
+
+        $crate::ArcDStr::from_static(&STATIC)
+    }};
+}
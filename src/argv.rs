@@ -0,0 +1,184 @@
+//! Module for argument and environment vectors, as used when spawning
+//! processes.
+
+use core::{
+    ffi::{c_char, c_int, CStr},
+    iter::FusedIterator,
+    marker::PhantomData,
+    str::Utf8Error,
+};
+
+use crate::{mem, DStr};
+
+mod error;
+pub use error::*;
+
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+pub use builder::*;
+
+#[cfg(feature = "alloc")]
+mod envp;
+#[cfg(feature = "alloc")]
+pub use envp::*;
+
+/// Returns an iterator over the strings of an argument vector, such as the
+/// one passed to `main` by a C host.
+///
+/// Each string is checked to be valid UTF-8 as it is reached. A negative
+/// `argc` is treated as zero.
+///
+/// # Safety
+///
+/// The caller must ensure the following:
+///
+/// - `argv` points to at least `argc` pointers, each of which is not null
+///   and points to a nul-terminated string.
+///
+/// - The array and the strings are not mutated or deallocated for the
+///   lifetime `'a`.
+#[inline]
+pub unsafe fn from_argv<'a>(argc: c_int, argv: *const *const c_char) -> Argv<'a> {
+    Argv {
+        ptr: argv,
+        len: usize::try_from(argc).unwrap_or(0),
+        marker: PhantomData,
+    }
+}
+
+/// Returns an iterator over the strings of a null-pointer-terminated
+/// environment vector, such as `environ` or the `envp` passed to `main`.
+///
+/// Each string is checked to be valid UTF-8 as it is reached. A null `envp`
+/// is treated as empty.
+///
+/// # Safety
+///
+/// The caller must ensure the following:
+///
+/// - `envp` points to an array of pointers terminated by a null pointer,
+///   each of which points to a nul-terminated string.
+///
+/// - The array and the strings are not mutated or deallocated for the
+///   lifetime `'a`, which notably includes calls to `setenv`.
+#[inline]
+pub unsafe fn from_envp<'a>(envp: *const *const c_char) -> Envp<'a> {
+    Envp {
+        ptr: envp,
+        marker: PhantomData,
+    }
+}
+
+/// # Safety
+///
+/// The caller must ensure that `ptr` points to a nul-terminated string, which
+/// lives for `'a`.
+#[inline]
+unsafe fn to_dstr<'a>(ptr: *const c_char) -> Result<&'a DStr, Utf8Error> {
+    // SAFETY: The caller ensures `ptr` points to a nul-terminated string.
+    let bytes = unsafe { CStr::from_ptr(ptr) }.to_bytes_with_nul();
+
+    mem::validate_utf8(bytes)?;
+
+    // SAFETY: We just checked that `bytes` is valid UTF-8, and a `CStr` has
+    //         no interior nuls.
+    Ok(unsafe { DStr::from_bytes_with_nul_unchecked(bytes) })
+}
+
+/// An iterator over the strings of an argument vector.
+///
+/// This is created by [`from_argv`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Argv<'a> {
+    ptr: *const *const c_char,
+    len: usize,
+    marker: PhantomData<&'a DStr>,
+}
+
+impl<'a> Iterator for Argv<'a> {
+    type Item = Result<&'a DStr, Utf8Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // SAFETY: There are `len` more pointers, as ensured by `from_argv`.
+        let string = unsafe {
+            let string = to_dstr(*self.ptr);
+            self.ptr = self.ptr.add(1);
+            string
+        };
+
+        self.len -= 1;
+
+        Some(string)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl DoubleEndedIterator for Argv<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+
+        // SAFETY: There are `len` more pointers, as ensured by `from_argv`.
+        Some(unsafe { to_dstr(*self.ptr.add(self.len)) })
+    }
+}
+
+impl ExactSizeIterator for Argv<'_> {}
+
+impl FusedIterator for Argv<'_> {}
+
+/// An iterator over the strings of a null-pointer-terminated environment
+/// vector.
+///
+/// This is created by [`from_envp`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Envp<'a> {
+    /// The next pointer, which is null once the iterator is exhausted.
+    ptr: *const *const c_char,
+    marker: PhantomData<&'a DStr>,
+}
+
+impl<'a> Iterator for Envp<'a> {
+    type Item = Result<&'a DStr, Utf8Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ptr.is_null() {
+            return None;
+        }
+
+        // SAFETY: The array is null-pointer-terminated, as ensured by
+        //         `from_envp`, and we stop at the null pointer.
+        let string = unsafe { *self.ptr };
+
+        if string.is_null() {
+            self.ptr = core::ptr::null();
+            return None;
+        }
+
+        // SAFETY: The array continues past a non-null pointer.
+        self.ptr = unsafe { self.ptr.add(1) };
+
+        // SAFETY: `string` points to a nul-terminated string, as ensured by
+        //         `from_envp`.
+        Some(unsafe { to_dstr(string) })
+    }
+}
+
+impl FusedIterator for Envp<'_> {}
@@ -0,0 +1,191 @@
+use core::{ffi::c_char, fmt, ptr};
+
+use alloc::vec::Vec;
+
+use crate::{dstr::NulError, DStr, DString};
+
+/// An owned list of arguments, along with a null-pointer-terminated array of
+/// pointers to them.
+///
+/// This is suitable for `execvp`, `posix_spawn`, or lists of extension names
+/// in Vulkan and OpenGL, as the pointers stay valid for as long as the builder
+/// is not changed.
+pub struct ArgvBuilder {
+    args: Vec<DString>,
+    /// A pointer to each argument, followed by a null pointer.
+    ptrs: Vec<*const c_char>,
+}
+
+// SAFETY: The pointers only point into the owned arguments.
+unsafe impl Send for ArgvBuilder {}
+
+// SAFETY: The pointers only point into the owned arguments, which are never
+//         mutated through a shared reference.
+unsafe impl Sync for ArgvBuilder {}
+
+impl ArgvBuilder {
+    /// Creates a new, empty [`ArgvBuilder`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> ArgvBuilder {
+        ArgvBuilder::with_capacity(0)
+    }
+
+    /// Creates a new, empty [`ArgvBuilder`] with room for at least `capacity`
+    /// arguments.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> ArgvBuilder {
+        let mut ptrs = Vec::with_capacity(capacity.saturating_add(1));
+        ptrs.push(ptr::null());
+
+        ArgvBuilder {
+            args: Vec::with_capacity(capacity),
+            ptrs,
+        }
+    }
+
+    /// Appends `arg` to the end of the arguments.
+    #[inline]
+    pub fn push(&mut self, arg: DString) {
+        // The string is on the heap, so the pointer stays valid when the
+        // `DString` itself is moved.
+        let ptr = arg.as_c_ptr();
+
+        self.args.push(arg);
+        self.ptrs.pop();
+        self.ptrs.push(ptr);
+        self.ptrs.push(ptr::null());
+    }
+
+    /// Appends a copy of `arg` to the end of the arguments.
+    #[inline]
+    pub fn push_dstr(&mut self, arg: &DStr) {
+        self.push(DString::from(arg));
+    }
+
+    /// Appends a copy of `arg` to the end of the arguments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arg` contains a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push_str(&mut self, arg: &str) {
+        if let Err(err) = self.try_push_str(arg) {
+            err._panic()
+        }
+    }
+
+    /// Try to append a copy of `arg` to the end of the arguments.
+    #[inline]
+    pub fn try_push_str(&mut self, arg: &str) -> Result<(), NulError> {
+        let mut string = DString::with_capacity(arg.len());
+        string.try_push_str(arg)?;
+
+        self.push(string);
+
+        Ok(())
+    }
+
+    /// Removes every argument.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ptrs.clear();
+        self.ptrs.push(ptr::null());
+        self.args.clear();
+    }
+
+    /// Returns the amount of arguments, excluding the null pointer.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Returns whether there are no arguments.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Returns the arguments.
+    #[inline]
+    #[must_use]
+    pub fn args(&self) -> &[DString] {
+        &self.args
+    }
+
+    /// Returns a pointer to the null-pointer-terminated array of arguments.
+    ///
+    /// The array and the strings it points to are valid until this
+    /// [`ArgvBuilder`] is changed or dropped. They must never be written to,
+    /// even if the function they are passed to takes `*const *mut c_char`.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.ptrs.as_ptr()
+    }
+}
+
+impl Default for ArgvBuilder {
+    #[inline]
+    fn default() -> Self {
+        ArgvBuilder::new()
+    }
+}
+
+impl Clone for ArgvBuilder {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.args.iter().cloned().collect()
+    }
+}
+
+impl fmt::Debug for ArgvBuilder {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.args).finish()
+    }
+}
+
+impl Extend<DString> for ArgvBuilder {
+    #[inline]
+    fn extend<T: IntoIterator<Item = DString>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+
+        self.args.reserve(lower);
+        self.ptrs.reserve(lower);
+
+        for arg in iter {
+            self.push(arg);
+        }
+    }
+}
+
+impl<'a> Extend<&'a DStr> for ArgvBuilder {
+    #[inline]
+    fn extend<T: IntoIterator<Item = &'a DStr>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(DString::from));
+    }
+}
+
+impl FromIterator<DString> for ArgvBuilder {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = DString>>(iter: T) -> Self {
+        let mut argv = ArgvBuilder::new();
+        argv.extend(iter);
+        argv
+    }
+}
+
+impl<'a> FromIterator<&'a DStr> for ArgvBuilder {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = &'a DStr>>(iter: T) -> Self {
+        let mut argv = ArgvBuilder::new();
+        argv.extend(iter);
+        argv
+    }
+}
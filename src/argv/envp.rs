@@ -0,0 +1,125 @@
+use core::{ffi::c_char, fmt};
+
+use crate::{DStr, DString};
+
+use super::{ArgvBuilder, EnvError};
+
+/// An owned list of `KEY=VALUE` environment entries, along with a
+/// null-pointer-terminated array of pointers to them.
+///
+/// This is suitable for `execve` and `posix_spawn`, as the pointers stay
+/// valid for as long as the builder is not changed.
+#[derive(Clone, Default)]
+pub struct EnvpBuilder {
+    inner: ArgvBuilder,
+}
+
+impl EnvpBuilder {
+    /// Creates a new, empty [`EnvpBuilder`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> EnvpBuilder {
+        EnvpBuilder {
+            inner: ArgvBuilder::new(),
+        }
+    }
+
+    /// Creates a new, empty [`EnvpBuilder`] with room for at least `capacity`
+    /// entries.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> EnvpBuilder {
+        EnvpBuilder {
+            inner: ArgvBuilder::with_capacity(capacity),
+        }
+    }
+
+    /// Appends a `KEY=VALUE` entry to the end of the environment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is empty or contains a `=` or a nul, or if `value`
+    /// contains a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, key: &str, value: &str) {
+        if let Err(err) = self.try_push(key, value) {
+            err._panic()
+        }
+    }
+
+    /// Try to append a `KEY=VALUE` entry to the end of the environment.
+    ///
+    /// Returns an error if `key` is empty or contains a `=` or a nul, or if
+    /// `value` contains a nul. Duplicate keys are not checked for.
+    pub fn try_push(&mut self, key: &str, value: &str) -> Result<(), EnvError> {
+        EnvError::check(key, value)?;
+
+        let mut entry = DString::with_capacity(key.len() + 1 + value.len());
+
+        // SAFETY: We just checked that neither part contains a nul.
+        unsafe {
+            entry.push_str_unchecked(key);
+            entry.push_str_unchecked("=");
+            entry.push_str_unchecked(value);
+        }
+
+        self.inner.push(entry);
+
+        Ok(())
+    }
+
+    /// Removes every entry.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Returns the amount of entries, excluding the null pointer.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns whether there are no entries.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the entries, each formatted as `KEY=VALUE`.
+    #[inline]
+    #[must_use]
+    pub fn entries(&self) -> &[DString] {
+        self.inner.args()
+    }
+
+    /// Returns an iterator over the keys and values of the entries.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DStr)> + '_ {
+        self.entries().iter().map(|entry| {
+            // Every entry contains a `=`, and the key does not.
+            entry.split_once('=').unwrap_or(("", DStr::EMPTY))
+        })
+    }
+
+    /// Returns a pointer to the null-pointer-terminated array of entries.
+    ///
+    /// The array and the strings it points to are valid until this
+    /// [`EnvpBuilder`] is changed or dropped. They must never be written to,
+    /// even if the function they are passed to takes `*const *mut c_char`.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.inner.as_ptr()
+    }
+}
+
+impl fmt::Debug for EnvpBuilder {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
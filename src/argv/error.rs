@@ -0,0 +1,96 @@
+use core::fmt;
+
+use crate::dstr::{msg, DStrError, ErrorKind};
+
+/// An error indicating that a key and value could not be added to an
+/// environment vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvError {
+    /// An empty key would make the entry start with its `=`.
+    EmptyKey,
+    KeyContainsEquals(usize),
+    KeyContainsNul(usize),
+    ValueContainsNul(usize),
+}
+
+impl EnvError {
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            EnvError::EmptyKey => msg!(EmptyKey),
+            EnvError::KeyContainsEquals(_) => msg!(KeyContainsEquals),
+            EnvError::KeyContainsNul(_) => msg!(KeyContainsNul),
+            EnvError::ValueContainsNul(_) => msg!(ValueContainsNul),
+        }
+    }
+
+    /// Checks that `key` and `value` can form a `KEY=VALUE` entry.
+    #[inline]
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) const fn check(key: &str, value: &str) -> Result<(), EnvError> {
+        let key = key.as_bytes();
+        let mut idx = 0;
+
+        if key.is_empty() {
+            return Err(EnvError::EmptyKey);
+        }
+
+        while idx < key.len() {
+            match key[idx] {
+                b'=' => return Err(EnvError::KeyContainsEquals(idx)),
+                0 => return Err(EnvError::KeyContainsNul(idx)),
+                _ => idx += 1,
+            }
+        }
+
+        match crate::mem::memchr(0, value.as_bytes()) {
+            Some(pos) => Err(EnvError::ValueContainsNul(pos)),
+            None => Ok(()),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        if let &Self::KeyContainsEquals(pos)
+        | &Self::KeyContainsNul(pos)
+        | &Self::ValueContainsNul(pos) = self
+        {
+            core::write!(f, " at pos {pos}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for EnvError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<EnvError> for DStrError {
+    #[inline]
+    fn from(value: EnvError) -> Self {
+        match value {
+            EnvError::EmptyKey => DStrError::new(ErrorKind::EmptyKey, None),
+            EnvError::KeyContainsEquals(pos) => {
+                DStrError::new(ErrorKind::KeyContainsEquals, Some(pos))
+            }
+            EnvError::KeyContainsNul(pos) => DStrError::new(ErrorKind::KeyContainsNul, Some(pos)),
+            EnvError::ValueContainsNul(pos) => {
+                DStrError::new(ErrorKind::ValueContainsNul, Some(pos))
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Module for the C functions exported with the `c-shims` feature.
+//!
+//! These are meant for freestanding targets without a C library, where C code
+//! linked into the same binary still needs them. Enabling the feature on a
+//! target with a C library makes these collide with its symbols.
+
+use core::{
+    ffi::{c_char, c_int, c_void},
+    ptr,
+};
+
+use crate::mem;
+
+/// Returns the length of the nul-terminated string `s`, excluding the nul.
+///
+/// # Safety
+///
+/// The caller must ensure that `s` points to a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn strlen(s: *const c_char) -> usize {
+    // This can not use `mem::find_nul`, which calls `strlen` itself.
+    //
+    // SAFETY: The caller ensures `s` points to a nul-terminated string.
+    unsafe { mem::strlen(s) }
+}
+
+/// Compares the nul-terminated strings `a` and `b`, returning a negative
+/// number, zero, or a positive number if `a` is less than, equal to, or
+/// greater than `b`.
+///
+/// # Safety
+///
+/// The caller must ensure that both pointers point to nul-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn strcmp(a: *const c_char, b: *const c_char) -> c_int {
+    // SAFETY: The caller ensures both pointers point to nul-terminated
+    //         strings.
+    unsafe { mem::strcmp(a, b) as c_int }
+}
+
+/// Returns a pointer to the first byte equal to `c` within the first `n`
+/// bytes of `s`, or a null pointer if there is none.
+///
+/// # Safety
+///
+/// The caller must ensure that `s` points to at least `n` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn memchr(s: *const c_void, c: c_int, n: usize) -> *mut c_void {
+    if n == 0 {
+        return ptr::null_mut();
+    }
+
+    // SAFETY: The caller ensures `s` points to `n` readable bytes.
+    let haystack = unsafe { core::slice::from_raw_parts(s.cast::<u8>(), n) };
+
+    match mem::memchr(c as u8, haystack) {
+        // SAFETY: The offset is within the haystack.
+        Some(offset) => unsafe { s.cast::<u8>().add(offset) }.cast_mut().cast(),
+        None => ptr::null_mut(),
+    }
+}
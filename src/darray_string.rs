@@ -0,0 +1,302 @@
+//! Module for fixed-capacity nul-terminated UTF-8 strings.
+
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    dstr::{IndexError, NulError, PushError},
+    DStr,
+};
+
+/// A nul-terminated UTF-8 string stored inline in a buffer of `N` bytes.
+///
+/// The buffer includes the nul terminator, so a [`DArrayString<N>`] can hold
+/// at most `N - 1` bytes, the same way as a `char[N]` would in C. This never
+/// allocates, and `N` must not be zero.
+///
+/// # Avoiding panics
+///
+/// Code that must never panic can stick to [`DArrayString::try_from_str`],
+/// [`DArrayString::try_push`], [`DArrayString::try_push_str`] and
+/// [`DArrayString::try_truncate`], which return an error instead of panicking
+/// when the input contains a nul, does not fit, or an index is invalid.
+#[derive(Clone, Copy)]
+pub struct DArrayString<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> DArrayString<N> {
+    /// Creates a new empty [`DArrayString`].
+    ///
+    /// This fails to compile if `N` is zero, as there would be no room for the
+    /// nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> DArrayString<N> {
+        const { assert!(N > 0, "a `DArrayString` needs room for the nul terminator") };
+
+        DArrayString {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Try to create a new [`DArrayString`] from `string`.
+    ///
+    /// Returns an error if `string` contains a nul or does not fit.
+    #[inline]
+    pub fn try_from_str(string: &str) -> Result<DArrayString<N>, PushError> {
+        let mut result = DArrayString::new();
+        result.try_push_str(string)?;
+
+        Ok(result)
+    }
+
+    /// Returns the capacity of this [`DArrayString`] in bytes, excluding the
+    /// byte reserved for the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+
+    /// Returns how many more bytes fit in this [`DArrayString`].
+    #[inline]
+    #[must_use]
+    pub const fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len
+    }
+
+    /// Returns whether this [`DArrayString`] is at full capacity.
+    #[inline]
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Appends `ch` to the end of this [`DArrayString`], before the nul
+    /// terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is a nul or does not fit.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, ch: char) {
+        if let Err(err) = self.try_push(ch) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `ch` to the end of this [`DArrayString`], before the nul
+    /// terminator.
+    ///
+    /// Returns an error if `ch` is a nul or does not fit.
+    #[inline]
+    pub fn try_push(&mut self, ch: char) -> Result<(), PushError> {
+        self.try_push_str(ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Appends `string` to the end of this [`DArrayString`], before the nul
+    /// terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul or does not fit.
+    #[inline]
+    #[track_caller]
+    pub fn push_str(&mut self, string: &str) {
+        if let Err(err) = self.try_push_str(string) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `string` to the end of this [`DArrayString`], before the
+    /// nul terminator.
+    ///
+    /// Returns an error if `string` contains a nul or does not fit, in which
+    /// case nothing is appended.
+    #[inline]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), PushError> {
+        if string.len() > self.remaining_capacity() {
+            return Err(PushError::CapacityExceeded);
+        }
+
+        NulError::check(string.as_bytes())?;
+
+        let end = self.len + string.len();
+
+        self.buf[self.len..end].copy_from_slice(string.as_bytes());
+        self.buf[end] = 0;
+        self.len = end;
+
+        Ok(())
+    }
+
+    /// Removes the last [`char`] before the nul terminator and returns it.
+    ///
+    /// Returns `None` if this [`DArrayString`] is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.last_char()?;
+        self.truncate(self.len - ch.len_utf8());
+
+        Some(ch)
+    }
+
+    /// Shortens this [`DArrayString`] to `new_len` bytes, excluding the nul
+    /// terminator, which is moved to the cut.
+    ///
+    /// If `new_len` is not smaller than [`DStr::len`], this does nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` does not lie on a char boundary.
+    #[inline]
+    #[track_caller]
+    pub fn truncate(&mut self, new_len: usize) {
+        if let Err(err) = self.try_truncate(new_len) {
+            err._panic()
+        }
+    }
+
+    /// Try to shorten this [`DArrayString`] to `new_len` bytes, excluding the
+    /// nul terminator, which is moved to the cut.
+    ///
+    /// If `new_len` is not smaller than [`DStr::len`], this does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `new_len` does not lie on a char boundary, in
+    /// which case nothing is removed.
+    #[inline]
+    pub fn try_truncate(&mut self, new_len: usize) -> Result<(), IndexError> {
+        if new_len < self.len {
+            IndexError::check(self.as_str(), new_len)?;
+
+            self.buf[new_len] = 0;
+            self.len = new_len;
+        }
+
+        Ok(())
+    }
+
+    /// Removes every [`char`] from this [`DArrayString`], leaving only the nul
+    /// terminator.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn as_dstr(&self) -> &DStr {
+        // SAFETY: `len` is always smaller than `N`.
+        let bytes = unsafe { core::slice::from_raw_parts(self.buf.as_ptr(), self.len + 1) };
+
+        // SAFETY: The first `len` bytes are always valid UTF-8 without nuls,
+        //         followed by the nul terminator.
+        unsafe { DStr::from_bytes_with_nul_unchecked(bytes) }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_dstr_mut(&mut self) -> &mut DStr {
+        // SAFETY: `len` is always smaller than `N`.
+        let bytes = unsafe { self.buf.get_unchecked_mut(..=self.len) };
+
+        // SAFETY: The first `len` bytes are always valid UTF-8 without nuls,
+        //         followed by the nul terminator.
+        unsafe { DStr::from_bytes_with_nul_unchecked_mut(bytes) }
+    }
+}
+
+impl<const N: usize> Default for DArrayString<N> {
+    #[inline]
+    fn default() -> Self {
+        DArrayString::new()
+    }
+}
+
+impl<const N: usize> PartialEq for DArrayString<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_dstr() == other.as_dstr()
+    }
+}
+
+impl<const N: usize> Eq for DArrayString<N> {}
+
+impl<const N: usize> PartialOrd for DArrayString<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for DArrayString<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_dstr().cmp(other.as_dstr())
+    }
+}
+
+impl<const N: usize> Hash for DArrayString<N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_dstr().hash(state)
+    }
+}
+
+impl<const N: usize> fmt::Debug for DArrayString<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dstr(), f)
+    }
+}
+
+impl<const N: usize> Deref for DArrayString<N> {
+    type Target = DStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_dstr()
+    }
+}
+
+impl<const N: usize> DerefMut for DArrayString<N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_dstr_mut()
+    }
+}
+
+/// Appends formatted output before the nul terminator.
+///
+/// Writing a nul, or more than fits, returns an error, in which case
+/// nothing of that piece is written.
+impl<const N: usize> fmt::Write for DArrayString<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.try_push(c).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for DArrayString<N> {
+    type Error = PushError;
+
+    #[inline]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        DArrayString::try_from_str(value)
+    }
+}
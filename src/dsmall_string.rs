@@ -0,0 +1,655 @@
+//! Module for small-string-optimized nul-terminated UTF-8 strings.
+
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::{size_of, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    slice::{from_raw_parts, from_raw_parts_mut},
+};
+
+use alloc::string::String;
+
+use crate::{
+    dstr::{IndexError, NulError},
+    DStr, DString,
+};
+
+/// The amount of bytes stored inline, excluding the nul terminator and the
+/// tag, which is 22 on 64-bit targets.
+const INLINE_CAPACITY: usize = size_of::<Heap>() - 2;
+
+/// Set in the last byte of an inline string.
+///
+/// The last byte of a heap string is the top byte of its capacity, which is at
+/// most `isize::MAX`, so this is never set there.
+const INLINE_TAG: u8 = 0x80;
+
+/// An owned nul-terminated UTF-8 string, which stores short strings inline.
+///
+/// Strings of up to 22 bytes on 64-bit targets, excluding the nul terminator,
+/// are kept inline without allocating, while the whole [`DSmallString`] is no
+/// larger than a [`DString`]. Anything longer is moved to a [`DString`] on
+/// the heap, where it stays even if the string gets shorter again.
+///
+/// # Avoiding panics
+///
+/// [`DSmallString::try_push`] and [`DSmallString::try_push_str`] return an
+/// error for nuls, and [`DSmallString::try_truncate`] for an index that is
+/// not on a char boundary. Growing past the inline capacity allocates, with
+/// the same caveats as for a [`DString`].
+pub struct DSmallString {
+    repr: Repr,
+}
+
+// A `DSmallString` must never be larger than the `DString` it beats.
+const _: () = assert!(size_of::<DSmallString>() == size_of::<DString>());
+
+// SAFETY: The string is owned, and the heap buffer is only ever accessed
+//         through it, the same as for a `DString`.
+unsafe impl Send for DSmallString {}
+
+// SAFETY: Shared references only ever read the string.
+unsafe impl Sync for DSmallString {}
+
+/// Either an inline string, or the raw parts of a [`DString`], which are told
+/// apart by [`INLINE_TAG`] in the last byte.
+#[repr(C)]
+union Repr {
+    inline: Inline,
+    heap: Heap,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Inline {
+    /// The first `len` bytes are valid UTF-8 without nuls, followed by a nul.
+    buf: [u8; INLINE_CAPACITY + 1],
+    /// [`INLINE_TAG`] combined with the length, which fits in the low bits.
+    tag: u8,
+}
+
+impl Inline {
+    #[inline]
+    const fn len(&self) -> usize {
+        (self.tag & !INLINE_TAG) as usize
+    }
+
+    #[inline]
+    fn set_len(&mut self, len: usize) {
+        // This can't truncate, as `len` is at most `INLINE_CAPACITY`.
+        self.tag = INLINE_TAG | len as u8;
+    }
+}
+
+/// The raw parts of a [`DString`], including the nul terminator.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Heap {
+    ptr: NonNull<u8>,
+    len: usize,
+    /// Stored as little-endian, so the top byte is always the last one.
+    cap: usize,
+}
+
+impl Heap {
+    #[inline]
+    fn new(string: DString) -> Heap {
+        let mut buf = ManuallyDrop::new(string.into_bytes_with_nul());
+
+        Heap {
+            // SAFETY: The pointer of a `Vec` is never null.
+            ptr: unsafe { NonNull::new_unchecked(buf.as_mut_ptr()) },
+            len: buf.len(),
+            cap: buf.capacity().to_le(),
+        }
+    }
+
+    /// Takes back ownership of the [`DString`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the parts are not used again.
+    #[inline]
+    unsafe fn into_dstring(self) -> DString {
+        let cap = usize::from_le(self.cap);
+
+        // SAFETY: The parts came from a `DString`, which was valid UTF-8, and
+        //         the caller ensures they are owned only once.
+        unsafe {
+            let string = String::from_raw_parts(self.ptr.as_ptr(), self.len, cap);
+            DString::from_string_with_nul_unchecked(string)
+        }
+    }
+}
+
+/// A [`DString`] borrowed from the raw parts of a heap string, to which it is
+/// written back once dropped, even while unwinding.
+struct HeapMut<'a> {
+    heap: &'a mut Heap,
+    string: ManuallyDrop<DString>,
+}
+
+impl Drop for HeapMut<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: `string` is never used again.
+        let string = unsafe { ManuallyDrop::take(&mut self.string) };
+        *self.heap = Heap::new(string);
+    }
+}
+
+impl Deref for HeapMut<'_> {
+    type Target = DString;
+
+    #[inline]
+    fn deref(&self) -> &DString {
+        &self.string
+    }
+}
+
+impl DerefMut for HeapMut<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut DString {
+        &mut self.string
+    }
+}
+
+impl DSmallString {
+    /// Creates a new empty [`DSmallString`].
+    ///
+    /// This does not allocate.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> DSmallString {
+        DSmallString {
+            repr: Repr {
+                inline: Inline {
+                    buf: [0; INLINE_CAPACITY + 1],
+                    tag: INLINE_TAG,
+                },
+            },
+        }
+    }
+
+    /// Creates a new empty [`DSmallString`] with room for at least `capacity`
+    /// bytes, excluding the nul terminator.
+    ///
+    /// This only allocates if `capacity` does not fit inline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity overflows.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> DSmallString {
+        if capacity <= INLINE_CAPACITY {
+            DSmallString::new()
+        } else {
+            DSmallString::from(DString::with_capacity(capacity))
+        }
+    }
+
+    /// Returns whether the string is stored inline, without an allocation.
+    #[inline]
+    #[must_use]
+    pub const fn is_inline(&self) -> bool {
+        // SAFETY: The last byte is initialized in both representations.
+        unsafe { self.repr.inline.tag & INLINE_TAG != 0 }
+    }
+
+    #[inline]
+    fn inline(&self) -> Option<&Inline> {
+        match self.is_inline() {
+            // SAFETY: We just checked that the string is inline.
+            true => Some(unsafe { &self.repr.inline }),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn inline_mut(&mut self) -> Option<&mut Inline> {
+        match self.is_inline() {
+            // SAFETY: We just checked that the string is inline.
+            true => Some(unsafe { &mut self.repr.inline }),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn heap(&self) -> Option<&Heap> {
+        match self.is_inline() {
+            true => None,
+            // SAFETY: We just checked that the string is on the heap.
+            false => Some(unsafe { &self.repr.heap }),
+        }
+    }
+
+    #[inline]
+    fn heap_mut(&mut self) -> Option<HeapMut<'_>> {
+        if self.is_inline() {
+            return None;
+        }
+
+        // SAFETY: We just checked that the string is on the heap, and the
+        //         guard writes the parts back before anything else can see
+        //         them.
+        let heap = unsafe { &mut self.repr.heap };
+        let string = ManuallyDrop::new(unsafe { heap.into_dstring() });
+
+        Some(HeapMut { heap, string })
+    }
+
+    /// Returns the capacity of this [`DSmallString`] in bytes, excluding the
+    /// byte reserved for the nul terminator.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        match self.heap() {
+            Some(heap) => usize::from_le(heap.cap) - 1,
+            None => INLINE_CAPACITY,
+        }
+    }
+
+    /// Appends `string` without checking for nuls, moving to the heap if it
+    /// doesn't fit inline.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `string` contains no nuls.
+    #[inline]
+    unsafe fn push_str_unchecked(&mut self, string: &str) {
+        if let Some(mut heap) = self.heap_mut() {
+            // SAFETY: The caller ensures `string` contains no nuls.
+            unsafe { heap.push_str_unchecked(string) };
+
+            return;
+        }
+
+        if let Some(inline) = self.inline_mut() {
+            if string.len() <= INLINE_CAPACITY - inline.len() {
+                let start = inline.len();
+                let end = start + string.len();
+
+                inline.buf[start..end].copy_from_slice(string.as_bytes());
+                inline.buf[end] = 0;
+                inline.set_len(end);
+
+                return;
+            }
+        }
+
+        let mut heap = DString::with_capacity(self.len().saturating_add(string.len()));
+        heap.push_dstr(self.as_dstr());
+
+        // SAFETY: The caller ensures `string` contains no nuls.
+        unsafe { heap.push_str_unchecked(string) };
+
+        // The inline string owns nothing, so it can just be overwritten.
+        self.repr = Repr {
+            heap: Heap::new(heap),
+        };
+    }
+
+    /// Appends `ch` to the end of this [`DSmallString`], before the nul
+    /// terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, ch: char) {
+        if let Err(err) = self.try_push(ch) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `ch` to the end of this [`DSmallString`], before the nul
+    /// terminator.
+    ///
+    /// Returns an error if `ch` is a nul.
+    #[inline]
+    pub fn try_push(&mut self, ch: char) -> Result<(), NulError> {
+        self.try_push_str(ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Appends `string` to the end of this [`DSmallString`], before the nul
+    /// terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push_str(&mut self, string: &str) {
+        if let Err(err) = self.try_push_str(string) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `string` to the end of this [`DSmallString`], before the
+    /// nul terminator.
+    ///
+    /// Returns an error if `string` contains a nul, in which case nothing is
+    /// appended.
+    #[inline]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), NulError> {
+        NulError::check(string.as_bytes())?;
+
+        // SAFETY: We just checked that `string` contains no nuls.
+        unsafe { self.push_str_unchecked(string) };
+
+        Ok(())
+    }
+
+    /// Appends `string` to the end of this [`DSmallString`], before the nul
+    /// terminator.
+    ///
+    /// Since a [`DStr`] is already known to be valid, this doesn't do any
+    /// checks.
+    #[inline]
+    pub fn push_dstr(&mut self, string: &DStr) {
+        // SAFETY: A `DStr` never contains nuls before its terminator.
+        unsafe { self.push_str_unchecked(string.as_str()) };
+    }
+
+    /// Removes the last [`char`] before the nul terminator and returns it.
+    ///
+    /// Returns `None` if this [`DSmallString`] is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.last_char()?;
+        self.truncate(self.len() - ch.len_utf8());
+
+        Some(ch)
+    }
+
+    /// Shortens this [`DSmallString`] to `new_len` bytes, excluding the nul
+    /// terminator, which is moved to the cut.
+    ///
+    /// If `new_len` is not smaller than [`DStr::len`], this does nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` does not lie on a char boundary.
+    #[inline]
+    #[track_caller]
+    pub fn truncate(&mut self, new_len: usize) {
+        if let Err(err) = self.try_truncate(new_len) {
+            err._panic()
+        }
+    }
+
+    /// Try to shorten this [`DSmallString`] to `new_len` bytes, excluding the
+    /// nul terminator, which is moved to the cut.
+    ///
+    /// If `new_len` is not smaller than [`DStr::len`], this does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `new_len` does not lie on a char boundary, in
+    /// which case nothing is removed.
+    #[inline]
+    pub fn try_truncate(&mut self, new_len: usize) -> Result<(), IndexError> {
+        if new_len >= self.len() {
+            return Ok(());
+        }
+
+        IndexError::check(self.as_str(), new_len)?;
+
+        if let Some(mut heap) = self.heap_mut() {
+            return heap.try_truncate(new_len);
+        }
+
+        if let Some(inline) = self.inline_mut() {
+            inline.buf[new_len] = 0;
+            inline.set_len(new_len);
+        }
+
+        Ok(())
+    }
+
+    /// Removes every [`char`] from this [`DSmallString`], leaving only the nul
+    /// terminator.
+    ///
+    /// This keeps any allocated capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Converts this [`DSmallString`] into a [`DString`].
+    ///
+    /// This only allocates if the string is stored inline.
+    #[inline]
+    #[must_use]
+    pub fn into_dstring(self) -> DString {
+        let this = ManuallyDrop::new(self);
+
+        match this.heap() {
+            // SAFETY: `this` is never dropped, so the parts are not used again.
+            Some(heap) => unsafe { heap.into_dstring() },
+            None => DString::from(this.as_dstr()),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_dstr(&self) -> &DStr {
+        let bytes = match self.heap() {
+            // SAFETY: The parts came from a `DString`, and are borrowed from
+            //         `self`.
+            Some(heap) => unsafe { from_raw_parts(heap.ptr.as_ptr(), heap.len) },
+            None => {
+                // SAFETY: The string is not on the heap, so it is inline, and
+                //         `len` is at most `INLINE_CAPACITY`.
+                unsafe {
+                    let inline = &self.repr.inline;
+                    inline.buf.get_unchecked(..=inline.len())
+                }
+            }
+        };
+
+        // SAFETY: Both representations hold valid UTF-8 without nuls,
+        //         followed by the nul terminator.
+        unsafe { DStr::from_bytes_with_nul_unchecked(bytes) }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_dstr_mut(&mut self) -> &mut DStr {
+        let bytes = match self.is_inline() {
+            true => {
+                // SAFETY: We just checked that the string is inline, and `len`
+                //         is at most `INLINE_CAPACITY`.
+                unsafe {
+                    let inline = &mut self.repr.inline;
+                    let len = inline.len();
+
+                    inline.buf.get_unchecked_mut(..=len)
+                }
+            }
+            // SAFETY: The parts came from a `DString`, and are borrowed
+            //         mutably from `self`.
+            false => unsafe { from_raw_parts_mut(self.repr.heap.ptr.as_ptr(), self.repr.heap.len) },
+        };
+
+        // SAFETY: Both representations hold valid UTF-8 without nuls,
+        //         followed by the nul terminator.
+        unsafe { DStr::from_bytes_with_nul_unchecked_mut(bytes) }
+    }
+}
+
+impl Drop for DSmallString {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(heap) = self.heap() {
+            // SAFETY: The string is being dropped, so the parts are not used
+            //         again.
+            drop(unsafe { heap.into_dstring() });
+        }
+    }
+}
+
+impl Clone for DSmallString {
+    /// Keeps a heap string on the heap, the same as the original.
+    #[inline]
+    fn clone(&self) -> Self {
+        match self.inline() {
+            Some(&inline) => DSmallString {
+                repr: Repr { inline },
+            },
+            None => DSmallString::from(DString::from(self.as_dstr())),
+        }
+    }
+}
+
+impl Default for DSmallString {
+    #[inline]
+    fn default() -> Self {
+        DSmallString::new()
+    }
+}
+
+impl PartialEq for DSmallString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_dstr() == other.as_dstr()
+    }
+}
+
+impl Eq for DSmallString {}
+
+impl PartialOrd for DSmallString {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DSmallString {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_dstr().cmp(other.as_dstr())
+    }
+}
+
+impl Hash for DSmallString {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_dstr().hash(state)
+    }
+}
+
+impl fmt::Debug for DSmallString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dstr(), f)
+    }
+}
+
+impl Deref for DSmallString {
+    type Target = DStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_dstr()
+    }
+}
+
+impl DerefMut for DSmallString {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_dstr_mut()
+    }
+}
+
+/// Appends formatted output before the nul terminator.
+///
+/// Writing a nul returns an error, in which case nothing is written.
+impl fmt::Write for DSmallString {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.try_push(c).map_err(|_| fmt::Error)
+    }
+}
+
+impl From<&DStr> for DSmallString {
+    #[inline]
+    fn from(value: &DStr) -> Self {
+        let mut string = DSmallString::with_capacity(value.len());
+        string.push_dstr(value);
+        string
+    }
+}
+
+impl From<DString> for DSmallString {
+    /// Keeps the [`DString`] on the heap, without copying it.
+    #[inline]
+    fn from(value: DString) -> Self {
+        DSmallString {
+            repr: Repr {
+                heap: Heap::new(value),
+            },
+        }
+    }
+}
+
+impl From<DSmallString> for DString {
+    #[inline]
+    fn from(value: DSmallString) -> Self {
+        value.into_dstring()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+
+    #[test]
+    fn moves_to_the_heap_past_the_inline_capacity() {
+        let mut small = DSmallString::new();
+        let mut expected = String::new();
+
+        for idx in 0..40 {
+            let ch = if idx % 3 == 0 { '\u{e9}' } else { 'a' };
+
+            small.push(ch);
+            expected.push(ch);
+
+            assert_eq!(small.as_str(), expected);
+            assert_eq!(small.is_inline(), expected.len() <= INLINE_CAPACITY);
+            assert!(small.capacity() >= expected.len());
+
+            let clone = small.clone();
+            assert_eq!(clone.as_str(), expected);
+            assert_eq!(clone.is_inline(), small.is_inline());
+        }
+
+        // SAFETY: Changing the case of ASCII never introduces a nul.
+        unsafe { small.as_dstr_mut().as_str_mut() }.make_ascii_uppercase();
+        expected.make_ascii_uppercase();
+        small.truncate(3);
+        expected.truncate(3);
+        assert_eq!(small.as_str(), expected);
+        assert!(!small.is_inline());
+
+        let string = small.into_dstring();
+        assert_eq!(string.as_str(), expected);
+
+        let small = DSmallString::from(string);
+        assert!(!small.is_inline());
+        assert_eq!(small.as_bytes_with_nul().last(), Some(&0));
+        assert_eq!(DSmallString::new().into_dstring().as_str(), "");
+    }
+}
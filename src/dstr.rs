@@ -1,16 +1,41 @@
 //! Module for borrowed nul-terminated UTF-8 strings.
 
 use core::{
+    cmp::Ordering,
     ffi::{c_char, CStr},
+    fmt,
+    hash::{Hash, Hasher},
     num::NonZeroUsize,
     slice::{from_raw_parts, from_raw_parts_mut},
-    str::{from_utf8_unchecked, from_utf8_unchecked_mut},
+    str::{from_utf8_unchecked, from_utf8_unchecked_mut, Bytes, CharIndices, Chars, FromStr},
 };
 
 mod error;
 pub use error::*;
 
-use crate::mem;
+mod iter;
+pub use iter::*;
+
+mod split;
+pub use split::*;
+
+mod matches;
+pub use matches::*;
+
+mod num;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "alloc")]
+use crate::DString;
+use crate::{
+    mem,
+    pattern::{DoubleEndedSearcher, Pattern, ReverseSearcher, Searcher},
+};
 
 /// A nul-terminated UTF-8 string.
 ///
@@ -38,14 +63,31 @@ use crate::mem;
 ///      underlying [`str`] / [`[u8]`].
 ///
 /// - The string is nul-terminated.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
 #[repr(transparent)]
 pub struct DStr {
     raw: str,
 }
 
+impl fmt::Debug for DStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Hashes the same as the [`str`] without the nul terminator, so a [`DStr`]
+/// can be looked up by a [`str`] and the other way around.
+impl Hash for DStr {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
 impl DStr {
-    // pub const EMPTY: &'static DStr = DStr::from_c_str(c"");
+    /// An empty [`DStr`], containing only the nul terminator.
+    pub const EMPTY: &'static DStr = DStr::from_str_with_nul("\0");
 }
 
 impl DStr {
@@ -79,6 +121,31 @@ impl DStr {
         self.len_with_nul().get() - 1
     }
 
+    /// Returns the amount of [`char`]s in this [`DStr`], excluding the nul
+    /// terminator.
+    ///
+    /// This is equivalent to `self.chars().count()`, but can be used in `const`
+    /// contexts.
+    #[inline]
+    #[must_use]
+    pub const fn count_chars(&self) -> usize {
+        let bytes = self.as_bytes();
+        let mut count = 0;
+        let mut index = 0;
+
+        while index < bytes.len() {
+            // Every byte except continuation bytes, which are `0b10xx_xxxx`,
+            // starts a char.
+            if (bytes[index] as i8) >= -0x40 {
+                count += 1;
+            }
+
+            index += 1;
+        }
+
+        count
+    }
+
     /// Returns whether this [`DStr`] is empty.
     ///
     /// **Note**: This returns whether `self.as_bytes()` has a length of zero.
@@ -95,6 +162,65 @@ impl DStr {
         unsafe { *self.raw.as_ptr() == 0 }
     }
 
+    /// Returns whether `index` lies on the boundary of a UTF-8 code point.
+    ///
+    /// The start and end of the string, excluding the nul terminator, are
+    /// considered boundaries. This behaves the same as [`str::is_char_boundary`].
+    #[inline]
+    #[must_use]
+    pub const fn is_char_boundary(&self, index: usize) -> bool {
+        if index > self.len() {
+            return false;
+        }
+
+        // SAFETY: `index` is at most `self.len()`, which is the position of the
+        //         nul, so it is within `raw`.
+        //
+        //         A byte is the start of a code point unless it is a continuation
+        //         byte, which are `0b10xx_xxxx`.
+        (unsafe { *self.raw.as_ptr().add(index) } as i8) >= -0x40
+    }
+
+    /// Returns the closest char boundary at or before `index`.
+    ///
+    /// If `index` is past the end of the string, `self.len()` is returned.
+    #[inline]
+    #[must_use]
+    pub const fn floor_char_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            return self.len();
+        }
+
+        let mut index = index;
+
+        // This always terminates, as `0` is a char boundary.
+        while !self.is_char_boundary(index) {
+            index -= 1;
+        }
+
+        index
+    }
+
+    /// Returns the closest char boundary at or after `index`.
+    ///
+    /// If `index` is past the end of the string, `self.len()` is returned.
+    #[inline]
+    #[must_use]
+    pub const fn ceil_char_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            return self.len();
+        }
+
+        let mut index = index;
+
+        // This always terminates, as `self.len()` is a char boundary.
+        while !self.is_char_boundary(index) {
+            index += 1;
+        }
+
+        index
+    }
+
     /// Converts this [`DStr`] to a `&[u8]` containing the nul
     /// terminator.
     #[inline]
@@ -271,7 +397,6 @@ impl DStr {
     }
 
     /// Try to create a [`DStr`] from a nul-terminated string.
-    #[must_use]
     pub const fn try_from_str_with_nul(string: &str) -> Result<&DStr, FromStrError> {
         match mem::memchr(0, string.as_bytes()) {
             Some(nul_pos) if nul_pos + 1 == string.len() => {
@@ -297,6 +422,57 @@ impl DStr {
         }
     }
 
+    /// Create a [`DStr`] from a nul-terminated string literal, such as in a
+    /// `static` initializer.
+    ///
+    /// This is the same as [`DStr::from_str_with_nul`], but the panic message
+    /// includes the position of an interior nul, so a bad literal fails the
+    /// build with a useful error when evaluated at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not nul-terminated or contains interior nuls.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_str_with_nul_or_panic(string: &'static str) -> &'static DStr {
+        match DStr::try_from_str_with_nul(string) {
+            Ok(string) => string,
+            Err(FromStrError::InteriorNul(nul_pos)) => panic_at_pos(msg!(InteriorNul), nul_pos),
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to create a [`DStr`] from a nul-terminated UTF-8 byte slice.
+    ///
+    /// The bytes are validated and searched for nuls in a single pass.
+    pub const fn try_from_bytes_with_nul(bytes: &[u8]) -> Result<&DStr, FromBytesError> {
+        match mem::validate_until_nul(bytes) {
+            Ok(Some(nul_pos)) if nul_pos + 1 == bytes.len() => {
+                Ok(unsafe { DStr::from_bytes_with_nul_unchecked(bytes) })
+            }
+            Ok(Some(nul_pos)) => Err(FromBytesError::InteriorNul(nul_pos)),
+            Ok(None) => Err(FromBytesError::NotNulTerminated),
+            Err(_) => Err(FromBytesError::InvalidUtf8(mem::utf8_error(bytes))),
+        }
+    }
+
+    /// Create a [`DStr`] from a nul-terminated UTF-8 byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes are not valid UTF-8, are not nul-terminated, or
+    /// contain interior nuls.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_bytes_with_nul(bytes: &[u8]) -> &DStr {
+        match DStr::try_from_bytes_with_nul(bytes) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+
     /// Create a mutable [`DStr`] from a mutable nul-terminated string without
     /// doing any checks.
     ///
@@ -335,4 +511,705 @@ impl DStr {
     pub unsafe fn from_bytes_with_nul_unchecked_mut(bytes: &mut [u8]) -> &mut DStr {
         unsafe { &mut *(bytes as *mut [u8] as *mut DStr) }
     }
+
+    /// Returns the suffix of this [`DStr`] starting at `start`, including the
+    /// nul terminator.
+    ///
+    /// Returns `None` if `start` is larger than [`DStr::len`] or does not lie
+    /// on a char boundary. This never panics.
+    #[inline]
+    #[must_use]
+    pub const fn get_suffix(&self, start: usize) -> Option<&DStr> {
+        if !self.is_char_boundary(start) {
+            return None;
+        }
+
+        // SAFETY: We just checked that `start` is at most `self.len()` and on
+        //         a char boundary.
+        Some(unsafe { self.get_suffix_unchecked(start) })
+    }
+
+    /// Returns the suffix of this [`DStr`] starting at `start`, including the
+    /// nul terminator.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `start` lies on a char boundary and is
+    /// at most `self.len()`.
+    #[inline]
+    #[must_use]
+    pub(crate) const unsafe fn get_suffix_unchecked(&self, start: usize) -> &DStr {
+        debug_assert!(start <= self.len(), "suffix start is out of bounds");
+
+        let bytes = self.as_bytes_with_nul();
+        let len = bytes.len() - start;
+        let ptr = unsafe { bytes.as_ptr().add(start) };
+
+        unsafe { DStr::from_bytes_with_nul_unchecked(from_raw_parts(ptr, len)) }
+    }
+}
+
+impl DStr {
+    /// Returns an iterator over the [`char`]s of this [`DStr`], excluding the
+    /// nul terminator.
+    #[inline]
+    pub fn chars(&self) -> Chars<'_> {
+        self.as_str().chars()
+    }
+
+    /// Returns an iterator over the [`char`]s of this [`DStr`] and their byte
+    /// positions, excluding the nul terminator.
+    #[inline]
+    pub fn char_indices(&self) -> CharIndices<'_> {
+        self.as_str().char_indices()
+    }
+
+    /// Returns an iterator over the bytes of this [`DStr`], excluding the
+    /// nul terminator.
+    #[inline]
+    pub fn bytes(&self) -> Bytes<'_> {
+        self.as_str().bytes()
+    }
+
+    /// Returns an iterator over the bytes of this [`DStr`], including the
+    /// nul terminator.
+    ///
+    /// The nul is always the last byte yielded.
+    #[inline]
+    pub fn bytes_with_nul(&self) -> BytesWithNul<'_> {
+        BytesWithNul {
+            inner: self.as_bytes_with_nul().iter(),
+        }
+    }
+
+    /// Returns an iterator over the lines of this [`DStr`], as string slices.
+    ///
+    /// Lines are split the same way as [`str::lines`]. The final line,
+    /// if it is not followed by a line ending, can be obtained as a [`DStr`]
+    /// through [`Lines::remainder`].
+    #[inline]
+    pub fn lines(&self) -> Lines<'_> {
+        Lines::new(self)
+    }
+
+    /// Returns an iterator over every suffix of this [`DStr`] that starts on a
+    /// char boundary, from longest to shortest.
+    ///
+    /// Every suffix of a [`DStr`] is itself a [`DStr`], so this doesn't copy.
+    /// The last suffix yielded is the empty string.
+    #[inline]
+    pub fn suffixes(&self) -> Suffixes<'_> {
+        Suffixes::new(self)
+    }
+
+    /// Returns an iterator over the [`char`]s of this [`DStr`] with full
+    /// Unicode case folding applied.
+    ///
+    /// This folds the same as `CaseFolding.txt` with the common and full
+    /// mappings, so `"Straße"` folds to `"strasse"`. Folding is meant for
+    /// caseless matching, not for display.
+    ///
+    /// This is only available with the `unicode-case` feature, since the case
+    /// mapping tables add to the size of the binary.
+    #[cfg(feature = "unicode-case")]
+    #[inline]
+    pub fn case_fold(&self) -> CaseFold<'_> {
+        CaseFold::new(self)
+    }
+
+    /// Returns an iterator that escapes each [`char`] of this [`DStr`] with
+    /// [`char::escape_debug`].
+    ///
+    /// The nul terminator is excluded, unless [`EscapeDebug::with_nul`] is used
+    /// to render it as `\0`.
+    #[inline]
+    pub fn escape_debug(&self) -> EscapeDebug<'_> {
+        EscapeDebug::new(self)
+    }
+
+    /// Returns an iterator that escapes each [`char`] of this [`DStr`] with
+    /// [`char::escape_default`].
+    ///
+    /// The nul terminator is excluded, unless [`EscapeDefault::with_nul`] is
+    /// used to render it as `\u{0}`.
+    #[inline]
+    pub fn escape_default(&self) -> EscapeDefault<'_> {
+        EscapeDefault::new(self)
+    }
+
+    /// Returns the first [`char`] of this [`DStr`], or `None` if it is empty.
+    #[inline]
+    #[must_use]
+    pub fn first_char(&self) -> Option<char> {
+        self.chars().next()
+    }
+
+    /// Returns the last [`char`] of this [`DStr`] before the nul terminator,
+    /// or `None` if it is empty.
+    #[inline]
+    #[must_use]
+    pub fn last_char(&self) -> Option<char> {
+        self.chars().next_back()
+    }
+
+    /// Splits the first [`char`] off of this [`DStr`], returning it along with
+    /// the rest of the string, which keeps the nul terminator.
+    ///
+    /// Returns `None` if this [`DStr`] is empty.
+    #[inline]
+    #[must_use]
+    pub fn split_first_char(&self) -> Option<(char, &DStr)> {
+        let c = self.first_char()?;
+
+        // SAFETY: The end of the first char is always a char boundary.
+        Some((c, unsafe { self.get_suffix_unchecked(c.len_utf8()) }))
+    }
+
+    /// Returns an iterator over the substrings of this [`DStr`] separated by
+    /// `pat`, excluding the nul terminator.
+    ///
+    /// This behaves the same as [`str::split`]. The final segment is always
+    /// a suffix of this [`DStr`], and can be obtained with its terminator
+    /// through [`Split::remainder`].
+    #[inline]
+    pub fn split<P: Pattern>(&self, pat: P) -> Split<'_, P> {
+        Split::new(self, pat)
+    }
+
+    /// Returns an iterator over the substrings of this [`DStr`] separated by
+    /// `pat`, in reverse order.
+    ///
+    /// This behaves the same as [`str::rsplit`].
+    #[inline]
+    pub fn rsplit<P: Pattern>(&self, pat: P) -> RSplit<'_, P>
+    where
+        for<'a> P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        RSplit::new(self, pat)
+    }
+
+    /// Returns an iterator over at most `n` substrings of this [`DStr`]
+    /// separated by `pat`.
+    ///
+    /// This behaves the same as [`str::splitn`]. The final segment is always
+    /// a suffix of this [`DStr`], and can be obtained with its terminator
+    /// through [`SplitN::remainder`].
+    #[inline]
+    pub fn splitn<P: Pattern>(&self, n: usize, pat: P) -> SplitN<'_, P> {
+        SplitN::new(self, n, pat)
+    }
+
+    /// Returns an iterator over at most `n` substrings of this [`DStr`]
+    /// separated by `pat`, starting from the end of the string.
+    ///
+    /// This behaves the same as [`str::rsplitn`].
+    #[inline]
+    pub fn rsplitn<P: Pattern>(&self, n: usize, pat: P) -> RSplitN<'_, P>
+    where
+        for<'a> P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        RSplitN::new(self, n, pat)
+    }
+
+    /// Returns an iterator over the substrings of this [`DStr`] separated by
+    /// `pat`, skipping a trailing empty substring.
+    ///
+    /// This behaves the same as [`str::split_terminator`]. The final segment
+    /// can be obtained with its terminator through
+    /// [`SplitTerminator::remainder`].
+    #[inline]
+    pub fn split_terminator<P: Pattern>(&self, pat: P) -> SplitTerminator<'_, P> {
+        SplitTerminator::new(self, pat)
+    }
+
+    /// Returns an iterator over the substrings of this [`DStr`] separated by
+    /// `pat`, in reverse order, skipping a trailing empty substring.
+    ///
+    /// This behaves the same as [`str::rsplit_terminator`].
+    #[inline]
+    pub fn rsplit_terminator<P: Pattern>(&self, pat: P) -> RSplitTerminator<'_, P>
+    where
+        for<'a> P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        RSplitTerminator::new(self, pat)
+    }
+
+    /// Returns an iterator over the substrings of this [`DStr`] terminated by
+    /// `pat`, where each substring keeps the matched part.
+    ///
+    /// This behaves the same as [`str::split_inclusive`]. The final segment
+    /// can be obtained with its terminator through
+    /// [`SplitInclusive::remainder`].
+    #[inline]
+    pub fn split_inclusive<P: Pattern>(&self, pat: P) -> SplitInclusive<'_, P> {
+        SplitInclusive::new(self, pat)
+    }
+
+    /// Returns an iterator over the words of this [`DStr`], separated by any
+    /// amount of whitespace.
+    ///
+    /// This behaves the same as [`str::split_whitespace`]. The words that are
+    /// left can be obtained with their terminator through
+    /// [`SplitWhitespace::remainder`].
+    #[inline]
+    pub fn split_whitespace(&self) -> SplitWhitespace<'_> {
+        SplitWhitespace::new(self)
+    }
+
+    /// Returns an iterator over the words of this [`DStr`], separated by any
+    /// amount of ASCII whitespace.
+    ///
+    /// This behaves the same as [`str::split_ascii_whitespace`]. The words that
+    /// are left can be obtained with their terminator through
+    /// [`SplitAsciiWhitespace::remainder`].
+    #[inline]
+    pub fn split_ascii_whitespace(&self) -> SplitAsciiWhitespace<'_> {
+        SplitAsciiWhitespace::new(self)
+    }
+
+    /// Returns an iterator over the matches of `pat` within this [`DStr`].
+    ///
+    /// This behaves the same as [`str::matches`].
+    #[inline]
+    pub fn matches<P: Pattern>(&self, pat: P) -> Matches<'_, P> {
+        Matches::new(self.as_str(), pat)
+    }
+
+    /// Returns an iterator over the matches of `pat` within this [`DStr`],
+    /// in reverse order.
+    ///
+    /// This behaves the same as [`str::rmatches`].
+    #[inline]
+    pub fn rmatches<P: Pattern>(&self, pat: P) -> RMatches<'_, P>
+    where
+        for<'a> P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        RMatches::new(self.as_str(), pat)
+    }
+
+    /// Returns an iterator over the matches of `pat` within this [`DStr`],
+    /// along with the byte position they start at.
+    ///
+    /// This behaves the same as [`str::match_indices`].
+    #[inline]
+    pub fn match_indices<P: Pattern>(&self, pat: P) -> MatchIndices<'_, P> {
+        MatchIndices::new(self.as_str(), pat)
+    }
+
+    /// Returns an iterator over the matches of `pat` within this [`DStr`],
+    /// along with the byte position they start at, in reverse order.
+    ///
+    /// This behaves the same as [`str::rmatch_indices`].
+    #[inline]
+    pub fn rmatch_indices<P: Pattern>(&self, pat: P) -> RMatchIndices<'_, P>
+    where
+        for<'a> P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        RMatchIndices::new(self.as_str(), pat)
+    }
+
+    /// Splits this [`DStr`] on the first occurrence of `delimiter`.
+    ///
+    /// Returns the part before the delimiter and the part after it, which
+    /// keeps the nul terminator.
+    ///
+    /// This behaves the same as [`str::split_once`].
+    #[inline]
+    #[must_use]
+    pub fn split_once<P: Pattern>(&self, delimiter: P) -> Option<(&str, &DStr)> {
+        let (start, end) = delimiter.into_searcher(self.as_str()).next_match()?;
+
+        // SAFETY: `Searcher` guarantees `start` and `end` lie on char boundaries.
+        unsafe {
+            Some((
+                self.as_str().get_unchecked(..start),
+                self.get_suffix_unchecked(end),
+            ))
+        }
+    }
+
+    /// Splits this [`DStr`] on the last occurrence of `delimiter`.
+    ///
+    /// This behaves the same as [`str::rsplit_once`].
+    #[inline]
+    #[must_use]
+    pub fn rsplit_once<P: Pattern>(&self, delimiter: P) -> Option<(&str, &str)>
+    where
+        for<'a> P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        let (start, end) = delimiter.into_searcher(self.as_str()).next_match_back()?;
+
+        // SAFETY: `Searcher` guarantees `start` and `end` lie on char boundaries.
+        unsafe {
+            Some((
+                self.as_str().get_unchecked(..start),
+                self.as_str().get_unchecked(end..),
+            ))
+        }
+    }
+}
+
+impl DStr {
+    /// Returns a string slice with leading and trailing whitespace removed.
+    ///
+    /// This behaves the same as [`str::trim`].
+    #[inline]
+    #[must_use]
+    pub fn trim(&self) -> &str {
+        self.as_str().trim()
+    }
+
+    /// Returns a [`DStr`] with leading whitespace removed.
+    ///
+    /// This behaves the same as [`str::trim_start`], though since only the
+    /// front is trimmed the result keeps the nul terminator.
+    #[inline]
+    #[must_use]
+    pub fn trim_start(&self) -> &DStr {
+        let trimmed = self.as_str().trim_start();
+
+        // SAFETY: `trimmed` is a suffix of `self`, so its start is a char boundary.
+        unsafe { self.get_suffix_unchecked(self.len() - trimmed.len()) }
+    }
+
+    /// Returns a string slice with trailing whitespace removed.
+    ///
+    /// This behaves the same as [`str::trim_end`].
+    #[inline]
+    #[must_use]
+    pub fn trim_end(&self) -> &str {
+        self.as_str().trim_end()
+    }
+
+    /// Returns a string slice with leading and trailing ASCII whitespace
+    /// removed.
+    ///
+    /// This behaves the same as [`str::trim_ascii`].
+    #[inline]
+    #[must_use]
+    pub const fn trim_ascii(&self) -> &str {
+        self.as_str().trim_ascii()
+    }
+
+    /// Returns a [`DStr`] with leading ASCII whitespace removed.
+    ///
+    /// This behaves the same as [`str::trim_ascii_start`], though since only
+    /// the front is trimmed the result keeps the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn trim_ascii_start(&self) -> &DStr {
+        let trimmed = self.as_bytes().trim_ascii_start();
+
+        // SAFETY: ASCII whitespace is always a single byte, so `trimmed` starts
+        //         on a char boundary.
+        unsafe { self.get_suffix_unchecked(self.len() - trimmed.len()) }
+    }
+
+    /// Returns a string slice with trailing ASCII whitespace removed.
+    ///
+    /// This behaves the same as [`str::trim_ascii_end`].
+    #[inline]
+    #[must_use]
+    pub const fn trim_ascii_end(&self) -> &str {
+        self.as_str().trim_ascii_end()
+    }
+
+    /// Parses this [`DStr`] into another type, excluding the nul terminator.
+    ///
+    /// This behaves the same as [`str::parse`].
+    #[inline]
+    pub fn parse<F: FromStr>(&self) -> Result<F, F::Err> {
+        self.as_str().parse()
+    }
+
+    /// Returns a string slice with every prefix and suffix matching `pat`
+    /// repeatedly removed.
+    ///
+    /// This behaves the same as [`str::trim_matches`].
+    #[must_use]
+    pub fn trim_matches<P: Pattern>(&self, pat: P) -> &str
+    where
+        for<'a> P::Searcher<'a>: DoubleEndedSearcher<'a>,
+    {
+        let mut start = 0;
+        let mut end = 0;
+        let mut matcher = pat.into_searcher(self.as_str());
+
+        if let Some((a, b)) = matcher.next_reject() {
+            start = a;
+            end = b;
+        }
+
+        if let Some((_, b)) = matcher.next_reject_back() {
+            end = b;
+        }
+
+        // SAFETY: `Searcher` guarantees `start` and `end` lie on char boundaries.
+        unsafe { self.as_str().get_unchecked(start..end) }
+    }
+
+    /// Returns a [`DStr`] with every prefix matching `pat` repeatedly removed.
+    ///
+    /// This behaves the same as [`str::trim_start_matches`], though since only
+    /// the front is trimmed the result keeps the nul terminator.
+    #[must_use]
+    pub fn trim_start_matches<P: Pattern>(&self, pat: P) -> &DStr {
+        let mut start = self.len();
+        let mut matcher = pat.into_searcher(self.as_str());
+
+        if let Some((a, _)) = matcher.next_reject() {
+            start = a;
+        }
+
+        // SAFETY: `Searcher` guarantees `start` lies on a char boundary.
+        unsafe { self.get_suffix_unchecked(start) }
+    }
+
+    /// Returns a string slice with every suffix matching `pat` repeatedly
+    /// removed.
+    ///
+    /// This behaves the same as [`str::trim_end_matches`].
+    #[must_use]
+    pub fn trim_end_matches<P: Pattern>(&self, pat: P) -> &str
+    where
+        for<'a> P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        let mut end = 0;
+        let mut matcher = pat.into_searcher(self.as_str());
+
+        if let Some((_, b)) = matcher.next_reject_back() {
+            end = b;
+        }
+
+        // SAFETY: `Searcher` guarantees `end` lies on a char boundary.
+        unsafe { self.as_str().get_unchecked(..end) }
+    }
+}
+
+impl DStr {
+    /// Compares two strings the same way as `strcmp` in C.
+    ///
+    /// This is the same as [`Ord`], but can be used in `const` contexts.
+    #[inline]
+    #[must_use]
+    pub const fn strcmp(&self, other: &DStr) -> Ordering {
+        // SAFETY: Both strings are nul-terminated.
+        unsafe { mem::strcmp(self.as_c_ptr(), other.as_c_ptr()) }
+    }
+
+    /// Compares at most the first `n` bytes of two strings, the same way as
+    /// `strncmp` in C.
+    #[inline]
+    #[must_use]
+    pub const fn strncmp(&self, other: &DStr, n: usize) -> Ordering {
+        // SAFETY: Both strings are nul-terminated, so neither is read past the
+        //         terminator.
+        unsafe { mem::strncmp(self.as_c_ptr(), other.as_c_ptr(), n) }
+    }
+
+    /// Compares two strings ignoring ASCII case, the same way as `strcasecmp`
+    /// in C in the `C` locale.
+    #[inline]
+    #[must_use]
+    pub const fn strcasecmp(&self, other: &DStr) -> Ordering {
+        // SAFETY: Both strings are nul-terminated.
+        unsafe { mem::strcasecmp(self.as_c_ptr(), other.as_c_ptr()) }
+    }
+
+    /// Compares at most the first `n` bytes of two strings ignoring ASCII
+    /// case, the same way as `strncasecmp` in C in the `C` locale.
+    #[inline]
+    #[must_use]
+    pub const fn strncasecmp(&self, other: &DStr, n: usize) -> Ordering {
+        // SAFETY: Both strings are nul-terminated, so neither is read past the
+        //         terminator.
+        unsafe { mem::strncasecmp(self.as_c_ptr(), other.as_c_ptr(), n) }
+    }
+
+    /// Compares two strings as versions, the same way as `strverscmp` in glibc.
+    ///
+    /// Runs of digits are compared by their numeric value, so `"file9"` comes
+    /// before `"file10"`, the same as `ls -v` sorts.
+    #[inline]
+    #[must_use]
+    pub const fn cmp_version(&self, other: &DStr) -> Ordering {
+        // SAFETY: Both strings are nul-terminated.
+        unsafe { mem::strverscmp(self.as_c_ptr(), other.as_c_ptr()) }
+    }
+
+    /// Returns whether two strings are equal ignoring case, using full Unicode
+    /// case folding.
+    ///
+    /// Unlike [`DStr::strcasecmp`], this is not limited to ASCII, so
+    /// `"STRASSE"` equals `"straße"`. See [`DStr::case_fold`].
+    #[cfg(feature = "unicode-case")]
+    #[inline]
+    #[must_use]
+    pub fn eq_ignore_case(&self, other: &DStr) -> bool {
+        if self.as_str().is_ascii() && other.as_str().is_ascii() {
+            return self.as_str().eq_ignore_ascii_case(other.as_str());
+        }
+
+        self.case_fold().eq(other.case_fold())
+    }
+
+    /// Compares two strings ignoring case, using full Unicode case folding.
+    ///
+    /// The folded strings are compared by code point. See
+    /// [`DStr::case_fold`].
+    #[cfg(feature = "unicode-case")]
+    #[inline]
+    #[must_use]
+    pub fn caseless_cmp(&self, other: &DStr) -> Ordering {
+        self.case_fold().cmp(other.case_fold())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DStr {
+    /// Creates a new [`DString`] by repeating this string `n` times.
+    ///
+    /// The result is built with a single allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity would overflow.
+    #[must_use]
+    pub fn repeat(&self, n: usize) -> DString {
+        let capacity = self
+            .len()
+            .checked_mul(n)
+            .and_then(|len| len.checked_add(1))
+            .expect("capacity overflow");
+
+        let mut string = String::with_capacity(capacity);
+
+        for _ in 0..n {
+            string.push_str(self.as_str());
+        }
+
+        string.push('\0');
+
+        // SAFETY: `self` contains no nuls, and we just added the terminator.
+        unsafe { DString::from_string_with_nul_unchecked(string) }
+    }
+
+    /// Replaces all matches of `from` with `to`, creating a new [`DString`].
+    ///
+    /// This behaves the same as [`str::replace`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `to` contains a nul.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn replace<P: Pattern>(&self, from: P, to: &str) -> DString {
+        match self.try_replace(from, to) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to replace all matches of `from` with `to`, creating a new
+    /// [`DString`].
+    ///
+    /// Returns an error if `to` contains a nul.
+    #[inline]
+    pub fn try_replace<P: Pattern>(&self, from: P, to: &str) -> Result<DString, NulError> {
+        NulError::check(to.as_bytes())?;
+
+        // SAFETY: We just checked that `to` contains no nuls.
+        Ok(unsafe { self.replacen_unchecked(from, to, usize::MAX) })
+    }
+
+    /// Replaces the first `count` matches of `from` with `to`, creating a new
+    /// [`DString`].
+    ///
+    /// This behaves the same as [`str::replacen`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `to` contains a nul.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn replacen<P: Pattern>(&self, from: P, to: &str, count: usize) -> DString {
+        match self.try_replacen(from, to, count) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to replace the first `count` matches of `from` with `to`, creating
+    /// a new [`DString`].
+    ///
+    /// Returns an error if `to` contains a nul.
+    #[inline]
+    pub fn try_replacen<P: Pattern>(
+        &self,
+        from: P,
+        to: &str,
+        count: usize,
+    ) -> Result<DString, NulError> {
+        NulError::check(to.as_bytes())?;
+
+        // SAFETY: We just checked that `to` contains no nuls.
+        Ok(unsafe { self.replacen_unchecked(from, to, count) })
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that `to` contains no nuls.
+    unsafe fn replacen_unchecked<P: Pattern>(&self, from: P, to: &str, count: usize) -> DString {
+        let mut string = String::with_capacity(self.len_with_nul().get());
+        let mut last_end = 0;
+
+        for (start, part) in self.match_indices(from).take(count) {
+            // SAFETY: Matches always start and end on char boundaries.
+            string.push_str(unsafe { self.as_str().get_unchecked(last_end..start) });
+            string.push_str(to);
+
+            last_end = start + part.len();
+        }
+
+        // SAFETY: `last_end` is either zero or the end of a match.
+        string.push_str(unsafe { self.as_str().get_unchecked(last_end..) });
+        string.push('\0');
+
+        // SAFETY: Neither `self` nor `to` contain nuls, and we just added the terminator.
+        unsafe { DString::from_string_with_nul_unchecked(string) }
+    }
+}
+
+#[cfg(feature = "std")]
+impl DStr {
+    /// Converts this [`DStr`] to a [`Path`], excluding the nul terminator.
+    ///
+    /// This does not copy on any platform.
+    #[inline]
+    #[must_use]
+    pub fn to_path(&self) -> &Path {
+        Path::new(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<Path> for DStr {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.to_path()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> TryFrom<&'a Path> for &'a DStr {
+    type Error = FromBytesError;
+
+    /// Returns an error if the path is not valid UTF-8, is not nul-terminated,
+    /// or contains interior nuls.
+    #[inline]
+    fn try_from(value: &'a Path) -> Result<Self, Self::Error> {
+        DStr::try_from_bytes_with_nul(value.as_os_str().as_encoded_bytes())
+    }
 }
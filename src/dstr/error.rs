@@ -1,5 +1,7 @@
 use core::{fmt, str::Utf8Error};
 
+use crate::mem;
+
 macro_rules! msg {
     (NotNulTerminated) => {
         "input was not nul-terminated"
@@ -13,11 +15,101 @@ macro_rules! msg {
     (InvalidUtf8) => {
         "input contains invalid utf-8"
     };
+    (ContainsNul) => {
+        "input contains a nul"
+    };
+    (CapacityExceeded) => {
+        "input exceeds the remaining capacity"
+    };
+    (InvalidUtf16) => {
+        "input contains invalid utf-16"
+    };
+    (InvalidModifiedUtf8) => {
+        "input contains invalid modified utf-8"
+    };
+    (EmptyString) => {
+        "input is an empty string"
+    };
+    (EmptyKey) => {
+        "key is an empty string"
+    };
+    (KeyContainsEquals) => {
+        "key contains an `=`"
+    };
+    (KeyContainsNul) => {
+        "key contains a nul"
+    };
+    (ValueContainsNul) => {
+        "value contains a nul"
+    };
+    (OutOfBounds) => {
+        "offset is out of bounds"
+    };
+    (NotCharBoundary) => {
+        "offset is not on a char boundary"
+    };
+    (NoDigits) => {
+        "input contains no digits"
+    };
+    (PosOverflow) => {
+        "number too large to fit in target type"
+    };
+    (NegOverflow) => {
+        "number too small to fit in target type"
+    };
     ($($tt:tt)*) => {
         compile_error!("unrecognized error message")
     };
 }
 
+pub(crate) use msg;
+
+/// Panics with `message` followed by the position, the same as the
+/// [`Display`](fmt::Display) of the errors, which unlike formatting integers
+/// also works in `const` contexts.
+#[track_caller]
+pub(crate) const fn panic_at_pos(message: &str, pos: usize) -> ! {
+    // Long enough for every message, the suffix and the digits of a `usize`.
+    let mut buf = [0u8; 96];
+    let mut len = append(&mut buf, 0, message.as_bytes());
+    len = append(&mut buf, len, b" at pos ");
+
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    let mut rest = pos;
+
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+
+        if rest == 0 {
+            break;
+        }
+    }
+
+    len = append(&mut buf, len, digits.split_at(start).1);
+
+    match core::str::from_utf8(buf.split_at(len).0) {
+        Ok(message) => panic!("{}", message),
+        Err(_) => panic!("{}", msg!(InvalidUtf8)),
+    }
+}
+
+/// Copies as much of `bytes` as fits into `buf` at `len`, returning the new
+/// length.
+const fn append(buf: &mut [u8], mut len: usize, bytes: &[u8]) -> usize {
+    let mut index = 0;
+
+    while index < bytes.len() && len < buf.len() {
+        buf[len] = bytes[index];
+        len += 1;
+        index += 1;
+    }
+
+    len
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FromStrError {
     NotNulTerminated,
@@ -36,6 +128,17 @@ impl FromStrError {
         }
     }
 
+    /// Returns the byte offset of the problem within the input, or `None` if
+    /// the problem is at the end of the input.
+    #[inline]
+    #[must_use]
+    pub const fn position(self) -> Option<usize> {
+        match self {
+            FromStrError::InteriorNul(nul_pos) => Some(nul_pos),
+            FromStrError::NotNulTerminated | FromStrError::MissingNul => None,
+        }
+    }
+
     #[inline(always)]
     pub(crate) const fn _panic<T>(self) -> T {
         panic!("{}", self.message())
@@ -64,8 +167,7 @@ impl fmt::Display for FromStrError {
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for FromStrError {
+impl core::error::Error for FromStrError {
     #[allow(deprecated)]
     #[inline]
     fn description(&self) -> &str {
@@ -116,6 +218,57 @@ impl FromBytesError {
         }
     }
 
+    /// Returns the byte offset of the problem within the input, or `None` if
+    /// the problem is at the end of the input.
+    ///
+    /// This is the position of the interior nul, or the start of the invalid
+    /// UTF-8 sequence.
+    #[inline]
+    #[must_use]
+    pub const fn position(self) -> Option<usize> {
+        match self {
+            FromBytesError::InteriorNul(nul_pos) => Some(nul_pos),
+            FromBytesError::InvalidUtf8(err) => Some(err.valid_up_to()),
+            FromBytesError::NotNulTerminated | FromBytesError::MissingNul => None,
+        }
+    }
+
+    /// Returns the length of the valid UTF-8 prefix of the input, or `None`
+    /// if the input is not invalid UTF-8.
+    ///
+    /// This behaves the same as [`Utf8Error::valid_up_to`].
+    #[inline]
+    #[must_use]
+    pub const fn valid_up_to(self) -> Option<usize> {
+        match self {
+            FromBytesError::InvalidUtf8(err) => Some(err.valid_up_to()),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the invalid UTF-8 sequence, or `None` if the
+    /// input is not invalid UTF-8 or ends in the middle of a sequence.
+    ///
+    /// This behaves the same as [`Utf8Error::error_len`].
+    #[inline]
+    #[must_use]
+    pub const fn error_len(self) -> Option<usize> {
+        match self {
+            FromBytesError::InvalidUtf8(err) => err.error_len(),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying [`Utf8Error`], if the input is not valid UTF-8.
+    #[inline]
+    #[must_use]
+    pub const fn utf8_error(self) -> Option<Utf8Error> {
+        match self {
+            FromBytesError::InvalidUtf8(err) => Some(err),
+            _ => None,
+        }
+    }
+
     #[inline(always)]
     pub(crate) const fn _panic<T>(self) -> T {
         panic!("{}", self.message())
@@ -126,7 +279,7 @@ impl fmt::Display for FromBytesError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(self.message())?;
 
-        if let &Self::InteriorNul(pos) = self {
+        if let Some(pos) = self.position() {
             core::write!(f, " at pos {pos}")?;
         }
 
@@ -134,10 +287,9 @@ impl fmt::Display for FromBytesError {
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for FromBytesError {
+impl core::error::Error for FromBytesError {
     #[inline]
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         match self {
             FromBytesError::InvalidUtf8(err) => Some(err),
             _ => None,
@@ -163,3 +315,554 @@ impl PartialEq<FromStrError> for FromBytesError {
         self.eq(&Self::from(*other))
     }
 }
+
+/// An error indicating that a string contained a nul where none is allowed,
+/// such as data being added to a [`DString`].
+///
+/// [`DString`]: crate::DString
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NulError {
+    nul_pos: usize,
+}
+
+impl NulError {
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(nul_pos: usize) -> NulError {
+        NulError { nul_pos }
+    }
+
+    /// Checks whether `bytes` contains a nul.
+    #[inline]
+    pub(crate) fn check(bytes: &[u8]) -> Result<(), NulError> {
+        match mem::find_byte(0, bytes) {
+            Some(nul_pos) => Err(NulError::new(nul_pos)),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks whether `bytes` contains a nul, in a `const` context.
+    #[inline]
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) const fn check_const(bytes: &[u8]) -> Result<(), NulError> {
+        match mem::memchr(0, bytes) {
+            Some(nul_pos) => Err(NulError::new(nul_pos)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the position of the nul within the input.
+    #[inline]
+    #[must_use]
+    pub const fn nul_position(self) -> usize {
+        self.nul_pos
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        msg!(ContainsNul)
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for NulError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+        core::write!(f, " at pos {}", self.nul_pos)
+    }
+}
+
+impl core::error::Error for NulError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+/// An error indicating that data could not be added to a fixed-capacity
+/// string, such as a [`DArrayString`].
+///
+/// [`DArrayString`]: crate::DArrayString
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushError {
+    ContainsNul(usize),
+    CapacityExceeded,
+}
+
+impl PushError {
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            PushError::ContainsNul(_) => msg!(ContainsNul),
+            PushError::CapacityExceeded => msg!(CapacityExceeded),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        if let &Self::ContainsNul(pos) = self {
+            core::write!(f, " at pos {pos}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for PushError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<NulError> for PushError {
+    #[inline]
+    fn from(value: NulError) -> Self {
+        PushError::ContainsNul(value.nul_position())
+    }
+}
+
+/// An error indicating that an index into a string is out of bounds, or does
+/// not lie on a char boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexError {
+    OutOfBounds(usize),
+    NotCharBoundary(usize),
+}
+
+impl IndexError {
+    /// Checks that `idx` lies on a char boundary of `string`, and is at most
+    /// its length.
+    #[inline]
+    pub const fn check(string: &str, idx: usize) -> Result<(), IndexError> {
+        if idx > string.len() {
+            Err(IndexError::OutOfBounds(idx))
+        } else if !string.is_char_boundary(idx) {
+            Err(IndexError::NotCharBoundary(idx))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            IndexError::OutOfBounds(_) => msg!(OutOfBounds),
+            IndexError::NotCharBoundary(_) => msg!(NotCharBoundary),
+        }
+    }
+
+    /// Returns the index that was invalid.
+    #[inline]
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            IndexError::OutOfBounds(idx) | IndexError::NotCharBoundary(idx) => idx,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        core::write!(f, " at pos {}", self.index())
+    }
+}
+
+impl core::error::Error for IndexError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+/// An error indicating that a string could not be edited in place, either
+/// because the inserted data contains a nul or because an index is invalid.
+///
+/// The position of [`EditError::ContainsNul`] is within the inserted data,
+/// while the others are indices into the edited string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditError {
+    ContainsNul(usize),
+    OutOfBounds(usize),
+    NotCharBoundary(usize),
+}
+
+impl EditError {
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            EditError::ContainsNul(_) => msg!(ContainsNul),
+            EditError::OutOfBounds(_) => msg!(OutOfBounds),
+            EditError::NotCharBoundary(_) => msg!(NotCharBoundary),
+        }
+    }
+
+    /// Returns the position of the nul, or the index that was invalid.
+    #[inline]
+    #[must_use]
+    pub const fn position(self) -> usize {
+        match self {
+            EditError::ContainsNul(pos)
+            | EditError::OutOfBounds(pos)
+            | EditError::NotCharBoundary(pos) => pos,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        core::write!(f, " at pos {}", self.position())
+    }
+}
+
+impl core::error::Error for EditError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<NulError> for EditError {
+    #[inline]
+    fn from(value: NulError) -> Self {
+        EditError::ContainsNul(value.nul_position())
+    }
+}
+
+impl From<IndexError> for EditError {
+    #[inline]
+    fn from(value: IndexError) -> Self {
+        match value {
+            IndexError::OutOfBounds(idx) => EditError::OutOfBounds(idx),
+            IndexError::NotCharBoundary(idx) => EditError::NotCharBoundary(idx),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseNumError {
+    NoDigits,
+    PosOverflow,
+    NegOverflow,
+}
+
+impl ParseNumError {
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            ParseNumError::NoDigits => msg!(NoDigits),
+            ParseNumError::PosOverflow => msg!(PosOverflow),
+            ParseNumError::NegOverflow => msg!(NegOverflow),
+        }
+    }
+}
+
+impl fmt::Display for ParseNumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl core::error::Error for ParseNumError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+/// The kind of a [`DStrError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The input was not nul-terminated.
+    NotNulTerminated,
+    /// The input contains a nul before its terminator.
+    InteriorNul,
+    /// The input does not contain a nul.
+    MissingNul,
+    /// The input is not valid UTF-8.
+    InvalidUtf8,
+    /// The input contains a nul where none is allowed.
+    ContainsNul,
+    /// The input does not fit in the remaining capacity.
+    CapacityExceeded,
+    /// The input is not valid UTF-16.
+    InvalidUtf16,
+    /// The input is not valid modified UTF-8.
+    InvalidModifiedUtf8,
+    /// The input is an empty string where none is allowed.
+    EmptyString,
+    /// The key of an environment variable is empty.
+    EmptyKey,
+    /// The key of an environment variable contains an `=`.
+    KeyContainsEquals,
+    /// The key of an environment variable contains a nul.
+    KeyContainsNul,
+    /// The value of an environment variable contains a nul.
+    ValueContainsNul,
+    /// An index or offset is past the end of the input.
+    OutOfBounds,
+    /// An index or offset does not lie on a char boundary.
+    NotCharBoundary,
+    /// The input contains no digits to parse.
+    NoDigits,
+    /// The parsed number is too large to fit in the target type.
+    PosOverflow,
+    /// The parsed number is too small to fit in the target type.
+    NegOverflow,
+}
+
+/// A single error type for every way a nul-terminated UTF-8 string can fail
+/// to be created, extended or parsed.
+///
+/// Every error of this crate converts into a [`DStrError`], so functions that
+/// call several constructors can return a single error type. The kind and
+/// position are always kept, but only [`FromStrError`], [`FromBytesError`],
+/// [`NulError`] and [`PushError`] can be converted back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DStrError {
+    kind: ErrorKind,
+    pos: Option<usize>,
+    utf8: Option<Utf8Error>,
+}
+
+impl DStrError {
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(kind: ErrorKind, pos: Option<usize>) -> DStrError {
+        DStrError {
+            kind,
+            pos,
+            utf8: None,
+        }
+    }
+
+    /// Returns the kind of this error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset of the problem within the input, or `None` if
+    /// the problem is at the end of the input.
+    #[inline]
+    #[must_use]
+    pub const fn position(self) -> Option<usize> {
+        self.pos
+    }
+
+    /// Returns the underlying [`Utf8Error`], if the input is not valid UTF-8.
+    ///
+    /// This is `None` for errors that report invalid UTF-8 without one, such
+    /// as a [`ValidateError`](crate::io::ValidateError).
+    #[inline]
+    #[must_use]
+    pub const fn utf8_error(self) -> Option<Utf8Error> {
+        self.utf8
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self.kind {
+            ErrorKind::NotNulTerminated => msg!(NotNulTerminated),
+            ErrorKind::InteriorNul => msg!(InteriorNul),
+            ErrorKind::MissingNul => msg!(MissingNul),
+            ErrorKind::InvalidUtf8 => msg!(InvalidUtf8),
+            ErrorKind::ContainsNul => msg!(ContainsNul),
+            ErrorKind::CapacityExceeded => msg!(CapacityExceeded),
+            ErrorKind::InvalidUtf16 => msg!(InvalidUtf16),
+            ErrorKind::InvalidModifiedUtf8 => msg!(InvalidModifiedUtf8),
+            ErrorKind::EmptyString => msg!(EmptyString),
+            ErrorKind::EmptyKey => msg!(EmptyKey),
+            ErrorKind::KeyContainsEquals => msg!(KeyContainsEquals),
+            ErrorKind::KeyContainsNul => msg!(KeyContainsNul),
+            ErrorKind::ValueContainsNul => msg!(ValueContainsNul),
+            ErrorKind::OutOfBounds => msg!(OutOfBounds),
+            ErrorKind::NotCharBoundary => msg!(NotCharBoundary),
+            ErrorKind::NoDigits => msg!(NoDigits),
+            ErrorKind::PosOverflow => msg!(PosOverflow),
+            ErrorKind::NegOverflow => msg!(NegOverflow),
+        }
+    }
+}
+
+impl fmt::Display for DStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        if let Some(pos) = self.pos {
+            core::write!(f, " at pos {pos}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for DStrError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self.utf8 {
+            Some(err) => Some(err),
+            None => None,
+        }
+    }
+
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<FromStrError> for DStrError {
+    #[inline]
+    fn from(value: FromStrError) -> Self {
+        FromBytesError::from(value).into()
+    }
+}
+
+impl From<FromBytesError> for DStrError {
+    #[inline]
+    fn from(value: FromBytesError) -> Self {
+        match value {
+            FromBytesError::NotNulTerminated => DStrError::new(ErrorKind::NotNulTerminated, None),
+            FromBytesError::InteriorNul(nul_pos) => {
+                DStrError::new(ErrorKind::InteriorNul, Some(nul_pos))
+            }
+            FromBytesError::MissingNul => DStrError::new(ErrorKind::MissingNul, None),
+            FromBytesError::InvalidUtf8(err) => DStrError {
+                kind: ErrorKind::InvalidUtf8,
+                pos: Some(err.valid_up_to()),
+                utf8: Some(err),
+            },
+        }
+    }
+}
+
+impl From<NulError> for DStrError {
+    #[inline]
+    fn from(value: NulError) -> Self {
+        DStrError::new(ErrorKind::ContainsNul, Some(value.nul_position()))
+    }
+}
+
+impl From<PushError> for DStrError {
+    #[inline]
+    fn from(value: PushError) -> Self {
+        match value {
+            PushError::ContainsNul(nul_pos) => {
+                DStrError::new(ErrorKind::ContainsNul, Some(nul_pos))
+            }
+            PushError::CapacityExceeded => DStrError::new(ErrorKind::CapacityExceeded, None),
+        }
+    }
+}
+
+impl From<IndexError> for DStrError {
+    #[inline]
+    fn from(value: IndexError) -> Self {
+        match value {
+            IndexError::OutOfBounds(idx) => DStrError::new(ErrorKind::OutOfBounds, Some(idx)),
+            IndexError::NotCharBoundary(idx) => {
+                DStrError::new(ErrorKind::NotCharBoundary, Some(idx))
+            }
+        }
+    }
+}
+
+impl From<EditError> for DStrError {
+    #[inline]
+    fn from(value: EditError) -> Self {
+        match value {
+            EditError::ContainsNul(nul_pos) => {
+                DStrError::new(ErrorKind::ContainsNul, Some(nul_pos))
+            }
+            EditError::OutOfBounds(idx) => DStrError::new(ErrorKind::OutOfBounds, Some(idx)),
+            EditError::NotCharBoundary(idx) => {
+                DStrError::new(ErrorKind::NotCharBoundary, Some(idx))
+            }
+        }
+    }
+}
+
+impl From<ParseNumError> for DStrError {
+    #[inline]
+    fn from(value: ParseNumError) -> Self {
+        let kind = match value {
+            ParseNumError::NoDigits => ErrorKind::NoDigits,
+            ParseNumError::PosOverflow => ErrorKind::PosOverflow,
+            ParseNumError::NegOverflow => ErrorKind::NegOverflow,
+        };
+
+        DStrError::new(kind, None)
+    }
+}
+
+impl TryFrom<DStrError> for FromBytesError {
+    type Error = DStrError;
+
+    /// Converts the error back, returning it unchanged if it is of a kind
+    /// that a [`FromBytesError`] can not represent.
+    #[inline]
+    fn try_from(value: DStrError) -> Result<Self, Self::Error> {
+        match (value.kind, value.pos, value.utf8) {
+            (ErrorKind::NotNulTerminated, _, _) => Ok(FromBytesError::NotNulTerminated),
+            (ErrorKind::InteriorNul, Some(nul_pos), _) => Ok(FromBytesError::InteriorNul(nul_pos)),
+            (ErrorKind::MissingNul, _, _) => Ok(FromBytesError::MissingNul),
+            (ErrorKind::InvalidUtf8, _, Some(err)) => Ok(FromBytesError::InvalidUtf8(err)),
+            _ => Err(value),
+        }
+    }
+}
+
+impl TryFrom<DStrError> for PushError {
+    type Error = DStrError;
+
+    /// Converts the error back, returning it unchanged if it is of a kind
+    /// that a [`PushError`] can not represent.
+    #[inline]
+    fn try_from(value: DStrError) -> Result<Self, Self::Error> {
+        match (value.kind, value.pos) {
+            (ErrorKind::ContainsNul, Some(nul_pos)) => Ok(PushError::ContainsNul(nul_pos)),
+            (ErrorKind::CapacityExceeded, _) => Ok(PushError::CapacityExceeded),
+            _ => Err(value),
+        }
+    }
+}
@@ -0,0 +1,453 @@
+#[cfg(feature = "unicode-case")]
+use core::str::Chars;
+use core::{fmt, iter::FusedIterator, slice};
+
+use crate::{mem, DStr};
+
+/// An iterator over the bytes of a [`DStr`], including the nul terminator.
+///
+/// This is created by [`DStr::bytes_with_nul`].
+///
+/// [`DStr::bytes_with_nul`]: crate::DStr::bytes_with_nul
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct BytesWithNul<'a> {
+    pub(crate) inner: slice::Iter<'a, u8>,
+}
+
+impl Iterator for BytesWithNul<'_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        self.inner.next().copied()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    #[inline]
+    fn last(self) -> Option<u8> {
+        self.inner.last().copied()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<u8> {
+        self.inner.nth(n).copied()
+    }
+}
+
+impl DoubleEndedIterator for BytesWithNul<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<u8> {
+        self.inner.next_back().copied()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<u8> {
+        self.inner.nth_back(n).copied()
+    }
+}
+
+impl ExactSizeIterator for BytesWithNul<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl FusedIterator for BytesWithNul<'_> {}
+
+/// An iterator over the lines of a [`DStr`], as string slices.
+///
+/// Lines are split the same way as [`str::lines`]. Since the final line
+/// may still own the nul terminator, it can be obtained as a [`DStr`]
+/// through [`Lines::remainder`].
+///
+/// This is created by [`DStr::lines`].
+///
+/// [`DStr::lines`]: crate::DStr::lines
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Lines<'a> {
+    haystack: &'a DStr,
+    start: usize,
+    end: usize,
+    finished: bool,
+}
+
+impl<'a> Lines<'a> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr) -> Lines<'a> {
+        Lines {
+            haystack,
+            start: 0,
+            end: haystack.len(),
+            finished: false,
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet.
+    ///
+    /// When the last line is not followed by a line ending, this is that
+    /// line, terminator included, once every line before it was yielded.
+    ///
+    /// Returns `None` if nothing is left, or if lines were taken from the back,
+    /// since the rest of the string would no longer be nul-terminated.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a DStr> {
+        if self.finished || self.start == self.end || self.end != self.haystack.len() {
+            return None;
+        }
+
+        // SAFETY: `start` always lies on a char boundary, as it is only ever
+        //         placed right after a `\n`.
+        Some(unsafe { self.haystack.get_suffix_unchecked(self.start) })
+    }
+}
+
+#[inline]
+fn strip_line_ending(line: &str) -> &str {
+    let Some(line) = line.strip_suffix('\n') else {
+        return line;
+    };
+
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        if self.finished {
+            return None;
+        }
+
+        if self.start == self.end {
+            self.finished = true;
+            return None;
+        }
+
+        let haystack = &self.haystack.as_str()[self.start..self.end];
+
+        match mem::find_byte(b'\n', haystack.as_bytes()) {
+            Some(pos) => {
+                self.start += pos + 1;
+                Some(strip_line_ending(&haystack[..pos + 1]))
+            }
+            None => {
+                self.finished = true;
+                Some(haystack)
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (0, Some(self.end - self.start))
+        }
+    }
+}
+
+impl DoubleEndedIterator for Lines<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if self.start == self.end {
+            self.finished = true;
+            return None;
+        }
+
+        let haystack = &self.haystack.as_str()[self.start..self.end];
+        let bytes = haystack.as_bytes();
+
+        let search_end = match bytes.last() {
+            Some(b'\n') => bytes.len() - 1,
+            _ => bytes.len(),
+        };
+
+        let line_start = bytes[..search_end]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |pos| pos + 1);
+
+        self.end = self.start + line_start;
+
+        Some(strip_line_ending(&haystack[line_start..]))
+    }
+}
+
+impl FusedIterator for Lines<'_> {}
+
+/// An iterator over every suffix of a [`DStr`] that starts on a char boundary,
+/// from longest to shortest.
+///
+/// Every suffix keeps the nul terminator, and the last one yielded is the
+/// empty string.
+///
+/// This is created by [`DStr::suffixes`].
+///
+/// [`DStr::suffixes`]: crate::DStr::suffixes
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Suffixes<'a> {
+    haystack: &'a DStr,
+    front: usize,
+    back: usize,
+    finished: bool,
+}
+
+impl<'a> Suffixes<'a> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr) -> Suffixes<'a> {
+        Suffixes {
+            haystack,
+            front: 0,
+            back: haystack.len(),
+            finished: false,
+        }
+    }
+}
+
+impl<'a> Iterator for Suffixes<'a> {
+    type Item = &'a DStr;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a DStr> {
+        if self.finished {
+            return None;
+        }
+
+        // SAFETY: `front` always lies on a char boundary.
+        let suffix = unsafe { self.haystack.get_suffix_unchecked(self.front) };
+
+        match suffix.first_char() {
+            Some(c) if self.front < self.back => self.front += c.len_utf8(),
+            _ => self.finished = true,
+        }
+
+        Some(suffix)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            let len = self.back - self.front;
+            (len.div_ceil(4) + 1, Some(len + 1))
+        }
+    }
+}
+
+impl<'a> DoubleEndedIterator for Suffixes<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a DStr> {
+        if self.finished {
+            return None;
+        }
+
+        // SAFETY: `back` always lies on a char boundary.
+        let suffix = unsafe { self.haystack.get_suffix_unchecked(self.back) };
+
+        if self.front < self.back {
+            self.back = self.haystack.floor_char_boundary(self.back - 1);
+        } else {
+            self.finished = true;
+        }
+
+        Some(suffix)
+    }
+}
+
+impl FusedIterator for Suffixes<'_> {}
+
+/// An iterator over the [`char`]s of a [`DStr`] with full Unicode case
+/// folding applied.
+///
+/// This is created by [`DStr::case_fold`].
+///
+/// [`DStr::case_fold`]: crate::DStr::case_fold
+#[cfg(feature = "unicode-case")]
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct CaseFold<'a> {
+    chars: Chars<'a>,
+    /// The folding of the current char, which is at most three chars long.
+    buf: [char; 3],
+    pos: u8,
+    len: u8,
+}
+
+#[cfg(feature = "unicode-case")]
+impl<'a> CaseFold<'a> {
+    #[inline]
+    pub(crate) fn new(string: &'a DStr) -> CaseFold<'a> {
+        CaseFold {
+            chars: string.chars(),
+            buf: ['\0'; 3],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Folds `ch` into the buffer.
+    fn fold(&mut self, ch: char) {
+        self.pos = 0;
+        self.len = 1;
+
+        match ch {
+            _ if ch.is_ascii() => self.buf[0] = ch.to_ascii_lowercase(),
+            // These are the only chars where folding differs from lowercasing
+            // the uppercase: the dotless `ı` folds to itself, the capital
+            // sharp s to `ss`, and Cherokee folds to uppercase.
+            '\u{131}' | '\u{13A0}'..='\u{13F5}' => self.buf[0] = ch,
+            '\u{1E9E}' => {
+                self.buf = ['s', 's', '\0'];
+                self.len = 2;
+            }
+            '\u{13F8}'..='\u{13FD}' | '\u{AB70}'..='\u{ABBF}' => {
+                self.buf[0] = ch.to_uppercase().next().unwrap_or(ch);
+            }
+            _ => {
+                self.len = 0;
+
+                for upper in ch.to_uppercase() {
+                    for lower in upper.to_lowercase() {
+                        self.buf[self.len as usize] = lower;
+                        self.len += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "unicode-case")]
+impl Iterator for CaseFold<'_> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        if self.pos == self.len {
+            let ch = self.chars.next()?;
+            self.fold(ch);
+        }
+
+        let ch = self.buf[self.pos as usize];
+        self.pos += 1;
+
+        Some(ch)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = (self.len - self.pos) as usize;
+        let (lower, upper) = self.chars.size_hint();
+
+        (
+            lower + buffered,
+            upper.and_then(|upper| upper.checked_mul(3)?.checked_add(buffered)),
+        )
+    }
+}
+
+#[cfg(feature = "unicode-case")]
+impl FusedIterator for CaseFold<'_> {}
+
+macro_rules! escape_types {
+    ($(
+        $(#[$meta:meta])*
+        struct $name:ident => $method:ident;
+    )*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct $name<'a> {
+            inner: core::str::$name<'a>,
+            nul: Option<core::char::$name>,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            pub(crate) fn new(string: &'a DStr) -> $name<'a> {
+                $name {
+                    inner: string.as_str().$method(),
+                    nul: None,
+                }
+            }
+
+            /// Also escapes the nul terminator at the end, making it visible.
+            #[inline]
+            pub fn with_nul(self) -> $name<'a> {
+                $name {
+                    inner: self.inner,
+                    nul: Some('\0'.$method()),
+                }
+            }
+        }
+
+        impl Iterator for $name<'_> {
+            type Item = char;
+
+            #[inline]
+            fn next(&mut self) -> Option<char> {
+                match self.inner.next() {
+                    Some(c) => Some(c),
+                    None => self.nul.as_mut()?.next(),
+                }
+            }
+        }
+
+        impl FusedIterator for $name<'_> {}
+
+        impl fmt::Display for $name<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.inner, f)?;
+
+                match &self.nul {
+                    Some(nul) => fmt::Display::fmt(nul, f),
+                    None => Ok(()),
+                }
+            }
+        }
+    )*};
+}
+
+escape_types! {
+    /// An iterator over the [`char`]s of a [`DStr`], escaped with
+    /// [`char::escape_debug`].
+    ///
+    /// This is created by [`DStr::escape_debug`]. It implements [`Display`],
+    /// and by default excludes the nul terminator, see [`EscapeDebug::with_nul`].
+    ///
+    /// [`Display`]: fmt::Display
+    /// [`DStr::escape_debug`]: crate::DStr::escape_debug
+    struct EscapeDebug => escape_debug;
+
+    /// An iterator over the [`char`]s of a [`DStr`], escaped with
+    /// [`char::escape_default`].
+    ///
+    /// This is created by [`DStr::escape_default`]. It implements [`Display`],
+    /// and by default excludes the nul terminator, see
+    /// [`EscapeDefault::with_nul`].
+    ///
+    /// [`Display`]: fmt::Display
+    /// [`DStr::escape_default`]: crate::DStr::escape_default
+    struct EscapeDefault => escape_default;
+}
@@ -0,0 +1,248 @@
+use core::{fmt, iter::FusedIterator};
+
+use super::split::pattern_iter_impls;
+use crate::pattern::{DoubleEndedSearcher, Pattern, ReverseSearcher, Searcher};
+
+/// The shared state behind the match iterators.
+pub(crate) struct MatchIndicesInternal<'a, P: Pattern> {
+    matcher: P::Searcher<'a>,
+}
+
+impl<'a, P: Pattern> Clone for MatchIndicesInternal<'a, P>
+where
+    P::Searcher<'a>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        MatchIndicesInternal {
+            matcher: self.matcher.clone(),
+        }
+    }
+}
+
+impl<'a, P: Pattern> fmt::Debug for MatchIndicesInternal<'a, P>
+where
+    P::Searcher<'a>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MatchIndicesInternal")
+            .field("matcher", &self.matcher)
+            .finish()
+    }
+}
+
+impl<'a, P: Pattern> MatchIndicesInternal<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a str, pat: P) -> Self {
+        MatchIndicesInternal {
+            matcher: pat.into_searcher(haystack),
+        }
+    }
+
+    /// Returns `haystack[start..end]`.
+    ///
+    /// # Safety
+    ///
+    /// Both `start` and `end` must come from the searcher.
+    #[inline]
+    unsafe fn slice(&self, start: usize, end: usize) -> &'a str {
+        unsafe { self.matcher.haystack().get_unchecked(start..end) }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        let (start, end) = self.matcher.next_match()?;
+
+        // SAFETY: `Searcher` guarantees `start` and `end` lie on char boundaries.
+        Some((start, unsafe { self.slice(start, end) }))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Option<(usize, &'a str)>
+    where
+        P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        let (start, end) = self.matcher.next_match_back()?;
+
+        // SAFETY: `Searcher` guarantees `start` and `end` lie on char boundaries.
+        Some((start, unsafe { self.slice(start, end) }))
+    }
+}
+
+/// An iterator over the matches of a pattern within a [`DStr`].
+///
+/// This is created by [`DStr::matches`].
+///
+/// [`DStr`]: crate::DStr
+/// [`DStr::matches`]: crate::DStr::matches
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Matches<'a, P: Pattern> {
+    inner: MatchIndicesInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> Matches<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a str, pat: P) -> Self {
+        Matches {
+            inner: MatchIndicesInternal::new(haystack, pat),
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for Matches<'a, P> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next().map(|(_, elt)| elt)
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for Matches<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.inner.next_back().map(|(_, elt)| elt)
+    }
+}
+
+impl<P: Pattern> FusedIterator for Matches<'_, P> {}
+
+/// An iterator over the matches of a pattern within a [`DStr`], in reverse
+/// order.
+///
+/// This is created by [`DStr::rmatches`].
+///
+/// [`DStr`]: crate::DStr
+/// [`DStr::rmatches`]: crate::DStr::rmatches
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RMatches<'a, P: Pattern> {
+    inner: MatchIndicesInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> RMatches<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a str, pat: P) -> Self {
+        RMatches {
+            inner: MatchIndicesInternal::new(haystack, pat),
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for RMatches<'a, P>
+where
+    P::Searcher<'a>: ReverseSearcher<'a>,
+{
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next_back().map(|(_, elt)| elt)
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for RMatches<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.inner.next().map(|(_, elt)| elt)
+    }
+}
+
+impl<'a, P: Pattern> FusedIterator for RMatches<'a, P> where P::Searcher<'a>: ReverseSearcher<'a> {}
+
+/// An iterator over the matches of a pattern within a [`DStr`], along with
+/// the byte position they start at.
+///
+/// This is created by [`DStr::match_indices`].
+///
+/// [`DStr`]: crate::DStr
+/// [`DStr::match_indices`]: crate::DStr::match_indices
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct MatchIndices<'a, P: Pattern> {
+    inner: MatchIndicesInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> MatchIndices<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a str, pat: P) -> Self {
+        MatchIndices {
+            inner: MatchIndicesInternal::new(haystack, pat),
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for MatchIndices<'a, P> {
+    type Item = (usize, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        self.inner.next()
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for MatchIndices<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<(usize, &'a str)> {
+        self.inner.next_back()
+    }
+}
+
+impl<P: Pattern> FusedIterator for MatchIndices<'_, P> {}
+
+/// An iterator over the matches of a pattern within a [`DStr`], along with
+/// the byte position they start at, in reverse order.
+///
+/// This is created by [`DStr::rmatch_indices`].
+///
+/// [`DStr`]: crate::DStr
+/// [`DStr::rmatch_indices`]: crate::DStr::rmatch_indices
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RMatchIndices<'a, P: Pattern> {
+    inner: MatchIndicesInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> RMatchIndices<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a str, pat: P) -> Self {
+        RMatchIndices {
+            inner: MatchIndicesInternal::new(haystack, pat),
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for RMatchIndices<'a, P>
+where
+    P::Searcher<'a>: ReverseSearcher<'a>,
+{
+    type Item = (usize, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for RMatchIndices<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<(usize, &'a str)> {
+        self.inner.next()
+    }
+}
+
+impl<'a, P: Pattern> FusedIterator for RMatchIndices<'a, P> where
+    P::Searcher<'a>: ReverseSearcher<'a>
+{
+}
+
+pattern_iter_impls!(Matches, RMatches, MatchIndices, RMatchIndices);
@@ -0,0 +1,445 @@
+//! Parsing of numbers with the semantics of `strtol` and friends in C.
+//!
+//! Unlike [`str::parse`], these parse the longest prefix that forms a number
+//! and return the rest of the string, which stays nul-terminated.
+
+use core::str::from_utf8_unchecked;
+
+use super::{DStr, ParseNumError};
+
+impl DStr {
+    /// Parses an integer from the start of this string, the same way as
+    /// `strtoll` in C in the `C` locale.
+    ///
+    /// Leading whitespace is skipped, followed by an optional sign and the
+    /// digits in `radix`, stopping at the first byte that is not one. A
+    /// `radix` of `16` accepts a `0x` or `0X` prefix, and a `radix` of `0`
+    /// picks hexadecimal for a `0x` prefix, octal for a `0` prefix and
+    /// decimal otherwise.
+    ///
+    /// Returns the value along with the rest of the string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no digits, or if the value does not fit
+    /// in an [`i64`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is neither `0` nor in the range `2..=36`.
+    #[inline]
+    #[track_caller]
+    pub const fn strtol(&self, radix: u32) -> Result<(i64, &DStr), ParseNumError> {
+        let int = match scan_int(self.as_bytes(), radix) {
+            Some(int) => int,
+            None => return Err(ParseNumError::NoDigits),
+        };
+
+        let value = match (int.negative, int.magnitude) {
+            (false, Some(m)) if m <= i64::MAX as u64 => m as i64,
+            (true, Some(m)) if m <= i64::MIN.unsigned_abs() => (m as i64).wrapping_neg(),
+            (false, _) => return Err(ParseNumError::PosOverflow),
+            (true, _) => return Err(ParseNumError::NegOverflow),
+        };
+
+        // SAFETY: Everything before `end` is ASCII.
+        Ok((value, unsafe { self.get_suffix_unchecked(int.end) }))
+    }
+
+    /// Parses an unsigned integer from the start of this string, the same way
+    /// as `strtoull` in C in the `C` locale.
+    ///
+    /// This accepts the same input as [`DStr::strtol`]. As in C, a leading
+    /// `-` negates the value in unsigned arithmetic, so `"-1"` parses as
+    /// [`u64::MAX`].
+    ///
+    /// Returns the value along with the rest of the string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no digits, or if the digits do not fit in
+    /// a [`u64`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is neither `0` nor in the range `2..=36`.
+    #[inline]
+    #[track_caller]
+    pub const fn strtoul(&self, radix: u32) -> Result<(u64, &DStr), ParseNumError> {
+        let int = match scan_int(self.as_bytes(), radix) {
+            Some(int) => int,
+            None => return Err(ParseNumError::NoDigits),
+        };
+
+        let value = match (int.negative, int.magnitude) {
+            (false, Some(m)) => m,
+            (true, Some(m)) => m.wrapping_neg(),
+            (_, None) => return Err(ParseNumError::PosOverflow),
+        };
+
+        // SAFETY: Everything before `end` is ASCII.
+        Ok((value, unsafe { self.get_suffix_unchecked(int.end) }))
+    }
+
+    /// Parses a floating point number from the start of this string, the same
+    /// way as `strtod` in C in the `C` locale.
+    ///
+    /// Leading whitespace is skipped, followed by an optional sign and either
+    /// a decimal number with an optional exponent, a hexadecimal number with
+    /// a `0x` prefix and an optional binary exponent, `inf`, `infinity` or
+    /// `nan`, ignoring case. A `nan` may be followed by a parenthesized
+    /// sequence of ASCII alphanumerics and underscores, which is ignored.
+    ///
+    /// Unlike in C, values out of range are not an error, and round to
+    /// infinity or zero the same as with [`str::parse`].
+    ///
+    /// Returns the value along with the rest of the string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no number.
+    pub fn strtod(&self) -> Result<(f64, &DStr), ParseNumError> {
+        let bytes = self.as_bytes();
+        let start = skip_space(bytes);
+        let mut i = start;
+
+        if matches!(byte(bytes, i), b'+' | b'-') {
+            i += 1;
+        }
+
+        let negative = byte(bytes, start) == b'-';
+
+        let (value, end) = if starts_with_ignore_case(bytes, i, b"infinity") {
+            (f64::INFINITY, i + 8)
+        } else if starts_with_ignore_case(bytes, i, b"inf") {
+            (f64::INFINITY, i + 3)
+        } else if starts_with_ignore_case(bytes, i, b"nan") {
+            (f64::NAN, skip_nan_payload(bytes, i + 3))
+        } else if let Some((value, end)) = scan_hex_float(bytes, i) {
+            (value, end)
+        } else {
+            let end = scan_dec_float(bytes, i).ok_or(ParseNumError::NoDigits)?;
+
+            // SAFETY: The digits, point and exponent are all ASCII.
+            let digits = unsafe { from_utf8_unchecked(&bytes[i..end]) };
+
+            match digits.parse::<f64>() {
+                Ok(value) => (value, end),
+                Err(_) => unreachable!("scanned an invalid float"),
+            }
+        };
+
+        let value = if negative { -value } else { value };
+
+        // SAFETY: Everything before `end` is ASCII.
+        Ok((value, unsafe { self.get_suffix_unchecked(end) }))
+    }
+}
+
+/// An integer scanned by [`scan_int`].
+struct Int {
+    negative: bool,
+    /// The magnitude of the integer, or `None` if it overflows a [`u64`].
+    magnitude: Option<u64>,
+    /// The end of the integer.
+    end: usize,
+}
+
+/// Scans an integer in `radix` from the start of `bytes`, returning `None` if
+/// there are no digits.
+#[inline]
+#[track_caller]
+const fn scan_int(bytes: &[u8], radix: u32) -> Option<Int> {
+    assert!(
+        radix == 0 || (radix >= 2 && radix <= 36),
+        "radix must be 0 or in the range 2..=36"
+    );
+
+    let mut i = skip_space(bytes);
+    let negative = byte(bytes, i) == b'-';
+
+    if matches!(byte(bytes, i), b'+' | b'-') {
+        i += 1;
+    }
+
+    let mut radix = radix;
+    let hex_prefix = byte(bytes, i) == b'0'
+        && matches!(byte(bytes, i + 1), b'x' | b'X')
+        && digit(byte(bytes, i + 2)) < 16;
+
+    if (radix == 0 || radix == 16) && hex_prefix {
+        radix = 16;
+        i += 2;
+    } else if radix == 0 {
+        radix = if byte(bytes, i) == b'0' { 8 } else { 10 };
+    }
+
+    let start = i;
+    let mut magnitude = Some(0u64);
+
+    while digit(byte(bytes, i)) < radix {
+        magnitude = match magnitude {
+            Some(m) => match m.checked_mul(radix as u64) {
+                Some(m) => m.checked_add(digit(byte(bytes, i)) as u64),
+                None => None,
+            },
+            None => None,
+        };
+
+        i += 1;
+    }
+
+    if i == start {
+        return None;
+    }
+
+    Some(Int {
+        negative,
+        magnitude,
+        end: i,
+    })
+}
+
+/// Scans a decimal float from `i`, returning its end.
+#[inline]
+fn scan_dec_float(bytes: &[u8], mut i: usize) -> Option<usize> {
+    let mut digits = 0;
+
+    while byte(bytes, i).is_ascii_digit() {
+        digits += 1;
+        i += 1;
+    }
+
+    if byte(bytes, i) == b'.' {
+        i += 1;
+
+        while byte(bytes, i).is_ascii_digit() {
+            digits += 1;
+            i += 1;
+        }
+    }
+
+    if digits == 0 {
+        return None;
+    }
+
+    Some(skip_exponent(bytes, i, b'e'))
+}
+
+/// Scans a hexadecimal float with a `0x` prefix from `i`, returning its value
+/// and end.
+fn scan_hex_float(bytes: &[u8], i: usize) -> Option<(f64, usize)> {
+    if byte(bytes, i) != b'0' || !matches!(byte(bytes, i + 1), b'x' | b'X') {
+        return None;
+    }
+
+    let mut i = i + 2;
+    let mut digits = 0;
+    // The first 16 significant digits, along with whether any of the rest are
+    // not zero.
+    let mut mantissa = 0u64;
+    let mut sticky = false;
+    let mut exp = 0i64;
+    let mut point = false;
+
+    loop {
+        let b = byte(bytes, i);
+
+        if b == b'.' && !point {
+            point = true;
+        } else if digit(b) < 16 {
+            let d = digit(b) as u64;
+
+            if mantissa >> 60 == 0 {
+                mantissa = mantissa << 4 | d;
+                exp -= 4 * point as i64;
+            } else {
+                sticky |= d != 0;
+                exp += 4 * !point as i64;
+            }
+
+            digits += 1;
+        } else {
+            break;
+        }
+
+        i += 1;
+    }
+
+    if digits == 0 {
+        return None;
+    }
+
+    let end = skip_exponent(bytes, i, b'p');
+
+    if end != i {
+        let mut j = i + 1;
+        let negative = byte(bytes, j) == b'-';
+
+        if matches!(byte(bytes, j), b'+' | b'-') {
+            j += 1;
+        }
+
+        let mut bin_exp = 0i64;
+
+        while j < end {
+            // Saturate well beyond the range of an `f64`.
+            bin_exp = (bin_exp * 10 + (bytes[j] - b'0') as i64).min(1 << 20);
+            j += 1;
+        }
+
+        exp += if negative { -bin_exp } else { bin_exp };
+    }
+
+    Some((compose_f64(mantissa, exp, sticky), end))
+}
+
+/// Returns `mantissa * 2^exp`, rounded to the nearest [`f64`] with ties to
+/// even. `sticky` is whether any bits below `mantissa` are set.
+fn compose_f64(mantissa: u64, exp: i64, sticky: bool) -> f64 {
+    if mantissa == 0 {
+        return 0.0;
+    }
+
+    let lz = mantissa.leading_zeros();
+    let mantissa = mantissa << lz;
+    // The exponent of the leading bit.
+    let exp = exp - lz as i64 + 63;
+
+    if exp > 1023 {
+        return f64::INFINITY;
+    }
+
+    if exp < -1022 {
+        // Subnormal, where the result is only the mantissa bits, and rounding
+        // up to the smallest normal number carries into the exponent bits.
+        let shift = (11 - 1022 - exp).min(65) as u32;
+
+        return f64::from_bits(round_shift(mantissa, shift, sticky));
+    }
+
+    let mut bits = round_shift(mantissa, 11, sticky);
+    let mut exp = exp;
+
+    if bits == 1 << 53 {
+        bits >>= 1;
+        exp += 1;
+
+        if exp > 1023 {
+            return f64::INFINITY;
+        }
+    }
+
+    // `bits` includes the implicit leading bit, which adds one to the
+    // exponent field.
+    f64::from_bits((((exp + 1022) as u64) << 52) + bits)
+}
+
+/// Shifts `value` right by `shift` bits, rounding to nearest with ties to
+/// even.
+#[inline]
+fn round_shift(value: u64, shift: u32, sticky: bool) -> u64 {
+    if shift > 64 {
+        return 0;
+    }
+
+    let (kept, rem) = match shift {
+        64 => (0, value),
+        _ => (value >> shift, value & ((1 << shift) - 1)),
+    };
+
+    let half = 1 << (shift - 1);
+    let round_up = rem > half || (rem == half && (sticky || kept & 1 == 1));
+
+    kept + round_up as u64
+}
+
+/// Skips an exponent starting with `marker` at `i`, which is only part of the
+/// number if it has digits.
+#[inline]
+fn skip_exponent(bytes: &[u8], i: usize, marker: u8) -> usize {
+    if !byte(bytes, i).eq_ignore_ascii_case(&marker) {
+        return i;
+    }
+
+    let mut j = i + 1;
+
+    if matches!(byte(bytes, j), b'+' | b'-') {
+        j += 1;
+    }
+
+    if !byte(bytes, j).is_ascii_digit() {
+        return i;
+    }
+
+    while byte(bytes, j).is_ascii_digit() {
+        j += 1;
+    }
+
+    j
+}
+
+/// Skips the parenthesized payload of a `nan` at `i`, if there is one.
+#[inline]
+fn skip_nan_payload(bytes: &[u8], i: usize) -> usize {
+    if byte(bytes, i) != b'(' {
+        return i;
+    }
+
+    let mut j = i + 1;
+
+    while byte(bytes, j).is_ascii_alphanumeric() || byte(bytes, j) == b'_' {
+        j += 1;
+    }
+
+    if byte(bytes, j) == b')' {
+        j + 1
+    } else {
+        i
+    }
+}
+
+#[inline]
+fn starts_with_ignore_case(bytes: &[u8], i: usize, prefix: &[u8]) -> bool {
+    match bytes.get(i..i + prefix.len()) {
+        Some(bytes) => bytes.eq_ignore_ascii_case(prefix),
+        None => false,
+    }
+}
+
+/// Skips leading whitespace as `isspace` in the `C` locale, which unlike
+/// [`u8::is_ascii_whitespace`] includes vertical tabs.
+#[inline]
+const fn skip_space(bytes: &[u8]) -> usize {
+    let mut i = 0;
+
+    while matches!(
+        byte(bytes, i),
+        b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r'
+    ) {
+        i += 1;
+    }
+
+    i
+}
+
+/// Returns the byte at `i`, or a nul past the end, like reading a C string.
+#[inline]
+const fn byte(bytes: &[u8], i: usize) -> u8 {
+    if i < bytes.len() {
+        bytes[i]
+    } else {
+        0
+    }
+}
+
+/// Returns the value of an ASCII alphanumeric as a digit, or [`u32::MAX`].
+#[inline]
+const fn digit(b: u8) -> u32 {
+    match b {
+        b'0'..=b'9' => (b - b'0') as u32,
+        b'a'..=b'z' => (b - b'a') as u32 + 10,
+        b'A'..=b'Z' => (b - b'A') as u32 + 10,
+        _ => u32::MAX,
+    }
+}
@@ -0,0 +1,837 @@
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    pattern::{DoubleEndedSearcher, Pattern, ReverseSearcher, Searcher},
+    DStr,
+};
+
+/// The shared state behind the split iterators.
+///
+/// This follows the same algorithm as the splitting iterators of [`str`],
+/// which is what keeps the semantics identical.
+pub(crate) struct SplitInternal<'a, P: Pattern> {
+    pub(crate) haystack: &'a DStr,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) matcher: P::Searcher<'a>,
+    pub(crate) allow_trailing_empty: bool,
+    pub(crate) finished: bool,
+}
+
+impl<'a, P: Pattern> Clone for SplitInternal<'a, P>
+where
+    P::Searcher<'a>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        SplitInternal {
+            haystack: self.haystack,
+            start: self.start,
+            end: self.end,
+            matcher: self.matcher.clone(),
+            allow_trailing_empty: self.allow_trailing_empty,
+            finished: self.finished,
+        }
+    }
+}
+
+impl<'a, P: Pattern> fmt::Debug for SplitInternal<'a, P>
+where
+    P::Searcher<'a>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitInternal")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("matcher", &self.matcher)
+            .field("allow_trailing_empty", &self.allow_trailing_empty)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl<'a, P: Pattern> SplitInternal<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr, pat: P, allow_trailing_empty: bool) -> Self {
+        SplitInternal {
+            haystack,
+            start: 0,
+            end: haystack.len(),
+            matcher: pat.into_searcher(haystack.as_str()),
+            allow_trailing_empty,
+            finished: false,
+        }
+    }
+
+    /// Returns `haystack[start..end]`.
+    ///
+    /// # Safety
+    ///
+    /// Both `start` and `end` must lie on char boundaries, which is always the
+    /// case for indices that come from a [`Searcher`].
+    #[inline]
+    unsafe fn slice(&self, start: usize, end: usize) -> &'a str {
+        unsafe { self.haystack.as_str().get_unchecked(start..end) }
+    }
+
+    #[inline]
+    pub(crate) fn get_end(&mut self) -> Option<&'a str> {
+        if !self.finished {
+            self.finished = true;
+
+            if self.allow_trailing_empty || self.end > self.start {
+                // SAFETY: `start` and `end` always lie on char boundaries.
+                return Some(unsafe { self.slice(self.start, self.end) });
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    pub(crate) fn next(&mut self) -> Option<&'a str> {
+        if self.finished {
+            return None;
+        }
+
+        match self.matcher.next_match() {
+            Some((a, b)) => {
+                // SAFETY: `Searcher` guarantees `a` and `b` lie on char boundaries.
+                let elt = unsafe { self.slice(self.start, a) };
+                self.start = b;
+                Some(elt)
+            }
+            None => self.get_end(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn next_back(&mut self) -> Option<&'a str>
+    where
+        P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        if self.finished {
+            return None;
+        }
+
+        if !self.allow_trailing_empty {
+            self.allow_trailing_empty = true;
+
+            match self.next_back() {
+                Some(elt) if !elt.is_empty() => return Some(elt),
+                _ if self.finished => return None,
+                _ => {}
+            }
+        }
+
+        match self.matcher.next_match_back() {
+            Some((a, b)) => {
+                // SAFETY: `Searcher` guarantees `a` and `b` lie on char boundaries.
+                let elt = unsafe { self.slice(b, self.end) };
+                self.end = a;
+                Some(elt)
+            }
+            None => {
+                self.finished = true;
+                // SAFETY: `start` and `end` always lie on char boundaries.
+                Some(unsafe { self.slice(self.start, self.end) })
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn next_inclusive(&mut self) -> Option<&'a str> {
+        if self.finished {
+            return None;
+        }
+
+        match self.matcher.next_match() {
+            Some((_, b)) => {
+                // SAFETY: `Searcher` guarantees `b` lies on a char boundary.
+                let elt = unsafe { self.slice(self.start, b) };
+                self.start = b;
+                Some(elt)
+            }
+            None => self.get_end(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn next_back_inclusive(&mut self) -> Option<&'a str>
+    where
+        P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        if self.finished {
+            return None;
+        }
+
+        if !self.allow_trailing_empty {
+            self.allow_trailing_empty = true;
+
+            match self.next_back_inclusive() {
+                Some(elt) if !elt.is_empty() => return Some(elt),
+                _ if self.finished => return None,
+                _ => {}
+            }
+        }
+
+        match self.matcher.next_match_back() {
+            Some((_, b)) => {
+                // SAFETY: `Searcher` guarantees `b` lies on a char boundary.
+                let elt = unsafe { self.slice(b, self.end) };
+                self.end = b;
+                Some(elt)
+            }
+            None => {
+                self.finished = true;
+                // SAFETY: `start` and `end` always lie on char boundaries.
+                Some(unsafe { self.slice(self.start, self.end) })
+            }
+        }
+    }
+
+    /// Returns the rest of the string if it is still nul-terminated.
+    #[inline]
+    pub(crate) fn remainder(&self) -> Option<&'a DStr> {
+        if self.finished || self.end != self.haystack.len() {
+            return None;
+        }
+
+        // SAFETY: `start` always lies on a char boundary and is at most `end`.
+        Some(unsafe { self.haystack.get_suffix_unchecked(self.start) })
+    }
+
+    /// Returns the rest of the string, regardless of the terminator.
+    #[inline]
+    pub(crate) fn remainder_str(&self) -> Option<&'a str> {
+        if self.finished {
+            return None;
+        }
+
+        // SAFETY: `start` and `end` always lie on char boundaries.
+        Some(unsafe { self.slice(self.start, self.end) })
+    }
+}
+
+/// Implements `Debug` and `Clone` for a pattern iterator wrapping some
+/// internal state in a field named `inner`.
+macro_rules! pattern_iter_impls {
+    ($($name:ident),* $(,)?) => {$(
+        impl<'a, P: Pattern> Clone for $name<'a, P>
+        where
+            P::Searcher<'a>: Clone,
+        {
+            #[inline]
+            fn clone(&self) -> Self {
+                $name {
+                    inner: self.inner.clone(),
+                }
+            }
+        }
+
+        impl<'a, P: Pattern> fmt::Debug for $name<'a, P>
+        where
+            P::Searcher<'a>: fmt::Debug,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("inner", &self.inner)
+                    .finish()
+            }
+        }
+    )*};
+}
+
+pub(crate) use pattern_iter_impls;
+
+/// An iterator over the substrings of a [`DStr`] separated by a pattern.
+///
+/// The final segment is always a suffix of the string, so it can be obtained
+/// as a [`DStr`] through [`Split::remainder`].
+///
+/// This is created by [`DStr::split`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Split<'a, P: Pattern> {
+    inner: SplitInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> Split<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr, pat: P) -> Self {
+        Split {
+            inner: SplitInternal::new(haystack, pat, true),
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet,
+    /// including the nul terminator.
+    ///
+    /// Before the last segment is yielded, the remainder is that segment.
+    ///
+    /// Returns `None` if the iterator is exhausted, or if segments were taken
+    /// from the back, since the rest of the string would no longer be
+    /// nul-terminated.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a DStr> {
+        self.inner.remainder()
+    }
+}
+
+impl<'a, P: Pattern> Iterator for Split<'a, P> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next()
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for Split<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.inner.next_back()
+    }
+}
+
+impl<P: Pattern> FusedIterator for Split<'_, P> {}
+
+/// An iterator over the substrings of a [`DStr`] separated by a pattern,
+/// in reverse order.
+///
+/// This is created by [`DStr::rsplit`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RSplit<'a, P: Pattern> {
+    inner: SplitInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> RSplit<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr, pat: P) -> Self {
+        RSplit {
+            inner: SplitInternal::new(haystack, pat, true),
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet.
+    ///
+    /// Returns `None` if the iterator is exhausted.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a str> {
+        self.inner.remainder_str()
+    }
+}
+
+impl<'a, P: Pattern> Iterator for RSplit<'a, P>
+where
+    P::Searcher<'a>: ReverseSearcher<'a>,
+{
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for RSplit<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.inner.next()
+    }
+}
+
+impl<'a, P: Pattern> FusedIterator for RSplit<'a, P> where P::Searcher<'a>: ReverseSearcher<'a> {}
+
+/// The shared state behind the counted split iterators.
+pub(crate) struct SplitNInternal<'a, P: Pattern> {
+    iter: SplitInternal<'a, P>,
+    count: usize,
+}
+
+impl<'a, P: Pattern> Clone for SplitNInternal<'a, P>
+where
+    P::Searcher<'a>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        SplitNInternal {
+            iter: self.iter.clone(),
+            count: self.count,
+        }
+    }
+}
+
+impl<'a, P: Pattern> fmt::Debug for SplitNInternal<'a, P>
+where
+    P::Searcher<'a>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitNInternal")
+            .field("iter", &self.iter)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+impl<'a, P: Pattern> SplitNInternal<'a, P> {
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.iter.get_end()
+            }
+            _ => {
+                self.count -= 1;
+                self.iter.next()
+            }
+        }
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str>
+    where
+        P::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.iter.get_end()
+            }
+            _ => {
+                self.count -= 1;
+                self.iter.next_back()
+            }
+        }
+    }
+}
+
+/// An iterator over at most `n` substrings of a [`DStr`] separated by a
+/// pattern.
+///
+/// The final segment is always a suffix of the string, so it can be obtained
+/// as a [`DStr`] through [`SplitN::remainder`].
+///
+/// This is created by [`DStr::splitn`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SplitN<'a, P: Pattern> {
+    inner: SplitNInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> SplitN<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr, count: usize, pat: P) -> Self {
+        SplitN {
+            inner: SplitNInternal {
+                iter: SplitInternal::new(haystack, pat, true),
+                count,
+            },
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet,
+    /// including the nul terminator.
+    ///
+    /// Before the last segment is yielded, the remainder is that segment.
+    ///
+    /// Returns `None` if the iterator is exhausted.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a DStr> {
+        match self.inner.count {
+            0 => None,
+            _ => self.inner.iter.remainder(),
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SplitN<'a, P> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.count))
+    }
+}
+
+impl<P: Pattern> FusedIterator for SplitN<'_, P> {}
+
+/// An iterator over at most `n` substrings of a [`DStr`] separated by a
+/// pattern, in reverse order.
+///
+/// This is created by [`DStr::rsplitn`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RSplitN<'a, P: Pattern> {
+    inner: SplitNInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> RSplitN<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr, count: usize, pat: P) -> Self {
+        RSplitN {
+            inner: SplitNInternal {
+                iter: SplitInternal::new(haystack, pat, true),
+                count,
+            },
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet.
+    ///
+    /// Returns `None` if the iterator is exhausted.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a str> {
+        match self.inner.count {
+            0 => None,
+            _ => self.inner.iter.remainder_str(),
+        }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for RSplitN<'a, P>
+where
+    P::Searcher<'a>: ReverseSearcher<'a>,
+{
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next_back()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.count))
+    }
+}
+
+impl<'a, P: Pattern> FusedIterator for RSplitN<'a, P> where P::Searcher<'a>: ReverseSearcher<'a> {}
+
+/// An iterator over the substrings of a [`DStr`] separated by a pattern,
+/// where a trailing empty substring is skipped.
+///
+/// Since the final segment is a suffix of the string, it can be obtained as a
+/// [`DStr`] through [`SplitTerminator::remainder`].
+///
+/// This is created by [`DStr::split_terminator`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SplitTerminator<'a, P: Pattern> {
+    inner: SplitInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> SplitTerminator<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr, pat: P) -> Self {
+        SplitTerminator {
+            inner: SplitInternal::new(haystack, pat, false),
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet,
+    /// including the nul terminator.
+    ///
+    /// Returns `None` if the iterator is exhausted, or if segments were taken
+    /// from the back, since the rest of the string would no longer be
+    /// nul-terminated.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a DStr> {
+        self.inner.remainder()
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SplitTerminator<'a, P> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next()
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for SplitTerminator<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.inner.next_back()
+    }
+}
+
+impl<P: Pattern> FusedIterator for SplitTerminator<'_, P> {}
+
+/// An iterator over the substrings of a [`DStr`] separated by a pattern,
+/// in reverse order, where a trailing empty substring is skipped.
+///
+/// This is created by [`DStr::rsplit_terminator`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RSplitTerminator<'a, P: Pattern> {
+    inner: SplitInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> RSplitTerminator<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr, pat: P) -> Self {
+        RSplitTerminator {
+            inner: SplitInternal::new(haystack, pat, false),
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet.
+    ///
+    /// Returns `None` if the iterator is exhausted.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a str> {
+        self.inner.remainder_str()
+    }
+}
+
+impl<'a, P: Pattern> Iterator for RSplitTerminator<'a, P>
+where
+    P::Searcher<'a>: ReverseSearcher<'a>,
+{
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for RSplitTerminator<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.inner.next()
+    }
+}
+
+impl<'a, P: Pattern> FusedIterator for RSplitTerminator<'a, P> where
+    P::Searcher<'a>: ReverseSearcher<'a>
+{
+}
+
+/// An iterator over the substrings of a [`DStr`] terminated by a pattern,
+/// where each substring keeps the matched part.
+///
+/// Since the final segment is a suffix of the string, it can be obtained as a
+/// [`DStr`] through [`SplitInclusive::remainder`].
+///
+/// This is created by [`DStr::split_inclusive`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SplitInclusive<'a, P: Pattern> {
+    inner: SplitInternal<'a, P>,
+}
+
+impl<'a, P: Pattern> SplitInclusive<'a, P> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr, pat: P) -> Self {
+        SplitInclusive {
+            inner: SplitInternal::new(haystack, pat, false),
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet,
+    /// including the nul terminator.
+    ///
+    /// Returns `None` if the iterator is exhausted, or if segments were taken
+    /// from the back, since the rest of the string would no longer be
+    /// nul-terminated.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a DStr> {
+        self.inner.remainder()
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SplitInclusive<'a, P> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.next_inclusive()
+    }
+}
+
+impl<'a, P: Pattern> DoubleEndedIterator for SplitInclusive<'a, P>
+where
+    P::Searcher<'a>: DoubleEndedSearcher<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.inner.next_back_inclusive()
+    }
+}
+
+impl<P: Pattern> FusedIterator for SplitInclusive<'_, P> {}
+
+pattern_iter_impls!(
+    Split,
+    RSplit,
+    SplitN,
+    RSplitN,
+    SplitTerminator,
+    RSplitTerminator,
+    SplitInclusive,
+);
+
+/// An iterator over the non-whitespace substrings of a [`DStr`], separated by
+/// any amount of whitespace.
+///
+/// Since the final word is a suffix of the string, it can be obtained as a
+/// [`DStr`] through [`SplitWhitespace::remainder`].
+///
+/// This is created by [`DStr::split_whitespace`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SplitWhitespace<'a> {
+    inner: SplitInternal<'a, fn(char) -> bool>,
+}
+
+impl<'a> SplitWhitespace<'a> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr) -> Self {
+        SplitWhitespace {
+            inner: SplitInternal::new(haystack, char::is_whitespace, true),
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet, starting
+    /// at the next word and including the nul terminator.
+    ///
+    /// Returns `None` if there are no words left, or if words were taken from
+    /// the back, since the rest of the string would no longer be
+    /// nul-terminated.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a DStr> {
+        let rest = self.inner.remainder()?;
+        let start = rest.as_str().find(|c: char| !c.is_whitespace())?;
+
+        // SAFETY: `find` always returns a char boundary.
+        Some(unsafe { rest.get_suffix_unchecked(start) })
+    }
+}
+
+impl<'a> Iterator for SplitWhitespace<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        loop {
+            match self.inner.next()? {
+                "" => continue,
+                word => return Some(word),
+            }
+        }
+    }
+}
+
+impl<'a> DoubleEndedIterator for SplitWhitespace<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        loop {
+            match self.inner.next_back()? {
+                "" => continue,
+                word => return Some(word),
+            }
+        }
+    }
+}
+
+impl FusedIterator for SplitWhitespace<'_> {}
+
+#[inline]
+fn is_ascii_whitespace(c: char) -> bool {
+    c.is_ascii_whitespace()
+}
+
+/// An iterator over the non-ASCII-whitespace substrings of a [`DStr`],
+/// separated by any amount of ASCII whitespace.
+///
+/// Since the final word is a suffix of the string, it can be obtained as a
+/// [`DStr`] through [`SplitAsciiWhitespace::remainder`].
+///
+/// This is created by [`DStr::split_ascii_whitespace`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SplitAsciiWhitespace<'a> {
+    inner: SplitInternal<'a, fn(char) -> bool>,
+}
+
+impl<'a> SplitAsciiWhitespace<'a> {
+    #[inline]
+    pub(crate) fn new(haystack: &'a DStr) -> Self {
+        SplitAsciiWhitespace {
+            inner: SplitInternal::new(haystack, is_ascii_whitespace, true),
+        }
+    }
+
+    /// Returns the part of the string that has not been yielded yet, starting
+    /// at the next word and including the nul terminator.
+    ///
+    /// Returns `None` if there are no words left, or if words were taken from
+    /// the back, since the rest of the string would no longer be
+    /// nul-terminated.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> Option<&'a DStr> {
+        let rest = self.inner.remainder()?;
+        let start = rest
+            .as_bytes()
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())?;
+
+        // SAFETY: ASCII whitespace is always a single byte, so the first byte
+        //         that isn't one starts a char.
+        Some(unsafe { rest.get_suffix_unchecked(start) })
+    }
+}
+
+impl<'a> Iterator for SplitAsciiWhitespace<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        loop {
+            match self.inner.next()? {
+                "" => continue,
+                word => return Some(word),
+            }
+        }
+    }
+}
+
+impl<'a> DoubleEndedIterator for SplitAsciiWhitespace<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        loop {
+            match self.inner.next_back()? {
+                "" => continue,
+                word => return Some(word),
+            }
+        }
+    }
+}
+
+impl FusedIterator for SplitAsciiWhitespace<'_> {}
@@ -0,0 +1,311 @@
+//! Module for allocating many nul-terminated UTF-8 strings from an arena.
+
+use core::{cell::RefCell, fmt};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{dstr::NulError, DStr};
+
+/// The capacity of the first chunk of an arena.
+const MIN_CHUNK: usize = 256;
+
+/// A bump arena for nul-terminated UTF-8 strings.
+///
+/// Strings are copied into large, append-only chunks along with their nul
+/// terminators, so allocating a string usually does not touch the heap. The
+/// strings live until the arena is dropped or [`DStrArena::reset`], which
+/// keeps the largest chunk around for reuse.
+///
+/// Allocating only needs a shared reference, so the arena can not be shared
+/// between threads.
+#[derive(Default)]
+pub struct DStrArena {
+    /// The buffers holding the strings, which are never reallocated.
+    chunks: RefCell<Vec<String>>,
+}
+
+impl DStrArena {
+    /// Creates a new, empty [`DStrArena`].
+    ///
+    /// This does not allocate until the first string is.
+    #[inline]
+    #[must_use]
+    pub fn new() -> DStrArena {
+        DStrArena {
+            chunks: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Creates a new, empty [`DStrArena`] with room for at least `capacity`
+    /// bytes of strings, including their nul terminators.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> DStrArena {
+        let arena = DStrArena::new();
+        arena
+            .chunks
+            .borrow_mut()
+            .push(String::with_capacity(capacity));
+        arena
+    }
+
+    /// Copies `string` into the arena.
+    #[inline]
+    pub fn alloc_dstr(&self, string: &DStr) -> &DStr {
+        // SAFETY: A `DStr` contains no interior nuls.
+        unsafe { self.alloc_str_unchecked(string.as_str()) }
+    }
+
+    /// Copies `string` into the arena, adding a nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul.
+    #[inline]
+    #[track_caller]
+    pub fn alloc_str(&self, string: &str) -> &DStr {
+        match self.try_alloc_str(string) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to copy `string` into the arena, adding a nul terminator.
+    #[inline]
+    pub fn try_alloc_str(&self, string: &str) -> Result<&DStr, NulError> {
+        NulError::check(string.as_bytes())?;
+
+        // SAFETY: We just checked that `string` contains no nuls.
+        Ok(unsafe { self.alloc_str_unchecked(string) })
+    }
+
+    /// Copies `string` into the arena, adding a nul terminator.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `string` contains no nuls.
+    pub(crate) unsafe fn alloc_str_unchecked(&self, string: &str) -> &DStr {
+        let mut chunks = self.chunks.borrow_mut();
+        let chunk = reserve(&mut chunks, 0, string.len() + 1);
+        let start = chunk.len();
+
+        chunk.push_str(string);
+        chunk.push('\0');
+
+        // SAFETY: The copy is a valid `DStr` as `string` contains no nuls.
+        let string: *const DStr =
+            unsafe { DStr::from_str_with_nul_unchecked(chunk.get_unchecked(start..)) };
+
+        // SAFETY: The chunk is never reallocated or cleared while the arena is
+        //         borrowed.
+        unsafe { &*string }
+    }
+
+    /// Formats `args` into the arena.
+    ///
+    /// This is usually called through [`format_args!`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output contains a nul, if a formatting trait
+    /// implementation returns an error, or if it allocates in this arena.
+    #[inline]
+    #[track_caller]
+    pub fn alloc_fmt(&self, args: fmt::Arguments<'_>) -> &DStr {
+        match self.try_alloc_fmt(args) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to format `args` into the arena.
+    ///
+    /// Returns an error if the output contains a nul, in which case the
+    /// position is relative to the start of the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a formatting trait implementation returns an error, or if it
+    /// allocates in this arena.
+    pub fn try_alloc_fmt(&self, args: fmt::Arguments<'_>) -> Result<&DStr, NulError> {
+        if let Some(string) = args.as_str() {
+            return self.try_alloc_str(string);
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+        let mut writer = FmtWriter {
+            chunks: &mut chunks,
+            len: 0,
+            error: None,
+        };
+
+        if fmt::write(&mut writer, args).is_err() {
+            let (len, error) = (writer.len, writer.error);
+
+            // Discard the partial output.
+            if let Some(chunk) = chunks.last_mut() {
+                chunk.truncate(chunk.len() - len);
+            }
+
+            return match error {
+                Some(err) => Err(err),
+                None => panic!(
+                    "a formatting trait implementation returned an error when the underlying stream did not"
+                ),
+            };
+        }
+
+        let len = writer.len;
+        let chunk = reserve(&mut chunks, len, 1);
+
+        chunk.push('\0');
+
+        let start = chunk.len() - len - 1;
+
+        // SAFETY: The output was checked for nuls as it was written.
+        let string: *const DStr =
+            unsafe { DStr::from_str_with_nul_unchecked(chunk.get_unchecked(start..)) };
+
+        // SAFETY: The chunk is never reallocated or cleared while the arena is
+        //         borrowed.
+        Ok(unsafe { &*string })
+    }
+
+    /// Frees every string in the arena, keeping the largest chunk for reuse.
+    #[inline]
+    pub fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+
+        // Chunks only grow, so the last one is the largest.
+        if let Some(mut last) = chunks.pop() {
+            last.clear();
+            chunks.clear();
+            chunks.push(last);
+        }
+    }
+
+    /// Returns the total amount of bytes allocated by the arena.
+    #[inline]
+    #[must_use]
+    pub fn allocated_bytes(&self) -> usize {
+        self.chunks.borrow().iter().map(String::capacity).sum()
+    }
+}
+
+impl fmt::Debug for DStrArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DStrArena")
+            .field("allocated_bytes", &self.allocated_bytes())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns a chunk with room for `additional` more bytes.
+///
+/// The `pending` bytes at the end of the last chunk are moved along if a new
+/// chunk is needed, so they stay contiguous.
+fn reserve(chunks: &mut Vec<String>, pending: usize, additional: usize) -> &mut String {
+    let fits = chunks
+        .last()
+        .is_some_and(|chunk| chunk.capacity() - chunk.len() >= additional);
+
+    if !fits {
+        let needed = pending.saturating_add(additional);
+        let capacity = chunks
+            .last()
+            .map_or(MIN_CHUNK, |chunk| chunk.capacity().saturating_mul(2))
+            .max(needed);
+
+        let mut chunk = String::with_capacity(capacity);
+
+        if let Some(last) = chunks.last_mut() {
+            let start = last.len() - pending;
+
+            chunk.push_str(&last[start..]);
+            last.truncate(start);
+        }
+
+        chunks.push(chunk);
+    }
+
+    // There is always at least one chunk at this point.
+    chunks.last_mut().unwrap()
+}
+
+/// Writes formatted output to the end of the last chunk, recording the output
+/// length and the position of the first nul.
+struct FmtWriter<'a> {
+    chunks: &'a mut Vec<String>,
+    len: usize,
+    error: Option<NulError>,
+}
+
+impl fmt::Write for FmtWriter<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if let Err(err) = NulError::check(s.as_bytes()) {
+            self.error = Some(NulError::new(self.len + err.nul_position()));
+            return Err(fmt::Error);
+        }
+
+        reserve(self.chunks, self.len, s.len()).push_str(s);
+        self.len += s.len();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+
+    /// Writes `self.0` one char at a time, so the output moves along when it
+    /// is split across chunks.
+    struct Chars<'a>(&'a str);
+
+    impl fmt::Display for Chars<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0
+                .chars()
+                .try_for_each(|ch| fmt::Write::write_char(f, ch))
+        }
+    }
+
+    #[test]
+    fn strings_stay_valid_across_chunks() {
+        let arena = DStrArena::new();
+        let mut strings = Vec::new();
+
+        for idx in 0..300 {
+            let body: String = "ab\u{e9}\u{1d11e}"
+                .chars()
+                .cycle()
+                .take(idx * 37 % 200)
+                .collect();
+
+            let string = arena.alloc_fmt(format_args!("{idx}:{}", Chars(&body)));
+            strings.push((string, format!("{idx}:{body}")));
+
+            // Fail partway through, after some of the output was written.
+            if idx % 7 == 0 {
+                let args = format_args!("{}{}", Chars(&body), Chars("x\0y"));
+                let err = arena.try_alloc_fmt(args).unwrap_err();
+                assert_eq!(err.nul_position(), body.len() + 1);
+            }
+
+            if idx % 11 == 0 {
+                let string = arena.alloc_str(&idx.to_string());
+                strings.push((string, idx.to_string()));
+            }
+        }
+
+        assert!(arena.chunks.borrow().len() > 2);
+
+        for (string, expected) in &strings {
+            assert_eq!(string.as_str(), expected);
+            assert_eq!(string.as_bytes_with_nul().last(), Some(&0));
+        }
+    }
+}
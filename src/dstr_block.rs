@@ -0,0 +1,292 @@
+//! Module for double-nul-terminated blocks of UTF-8 strings.
+
+use core::{ffi::c_char, fmt, str::from_utf8_unchecked};
+
+use crate::{
+    dstr::FromBytesError,
+    dstr_list::{DStrList, Iter},
+    mem, DStr,
+};
+
+mod error;
+pub use error::*;
+
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+pub use builder::*;
+
+/// A block of nul-terminated UTF-8 strings, terminated by an empty string,
+/// such as a `REG_MULTI_SZ` value or a `CreateProcess` environment block.
+///
+/// Unlike a [`DStrList`], the end of the block is marked by an extra nul, so
+/// the strings in a block can never be empty. An empty block is a single nul.
+///
+/// # Safety
+///
+/// - The internal representation must always be valid UTF-8.
+///
+/// - The block ends with an empty string, and contains no other empty
+///   strings, so the only two consecutive nuls are the last two bytes, or
+///   the block is a single nul.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct DStrBlock {
+    raw: str,
+}
+
+impl DStrBlock {
+    /// An empty [`DStrBlock`], containing only the terminating nul.
+    pub const EMPTY: &'static DStrBlock = DStrBlock::from_str("\0");
+
+    /// Create a [`DStrBlock`] from a string without doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `string` is a valid block.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn from_str_unchecked(string: &str) -> &DStrBlock {
+        // SAFETY: `DStrBlock` is a transparent wrapper around `str`, and the
+        //         caller ensures the string is a valid block.
+        unsafe { &*(string as *const str as *const DStrBlock) }
+    }
+
+    /// Create a [`DStrBlock`] from a byte slice without doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` is valid UTF-8, and is a valid
+    /// block.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &DStrBlock {
+        // SAFETY: The caller ensures the bytes are valid UTF-8 and a valid
+        //         block.
+        unsafe { DStrBlock::from_str_unchecked(from_utf8_unchecked(bytes)) }
+    }
+
+    /// Try to create a [`DStrBlock`] from a string.
+    ///
+    /// Returns an error if the string does not end with an empty string, or
+    /// contains an empty string before the end, in which case the position
+    /// is that of the nul terminating the block early.
+    pub const fn try_from_str(string: &str) -> Result<&DStrBlock, FromBytesError> {
+        let bytes = string.as_bytes();
+        let len = bytes.len();
+        let mut start = 0;
+        let mut idx = 0;
+
+        while idx < len {
+            if bytes[idx] == 0 {
+                if idx == start {
+                    return if idx + 1 == len {
+                        // SAFETY: The first empty string is at the very end.
+                        Ok(unsafe { DStrBlock::from_str_unchecked(string) })
+                    } else {
+                        Err(FromBytesError::InteriorNul(idx))
+                    };
+                }
+
+                start = idx + 1;
+            }
+
+            idx += 1;
+        }
+
+        Err(FromBytesError::NotNulTerminated)
+    }
+
+    /// Create a [`DStrBlock`] from a string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid block.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_str(string: &str) -> &DStrBlock {
+        match DStrBlock::try_from_str(string) {
+            Ok(block) => block,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to create a [`DStrBlock`] from a byte slice.
+    ///
+    /// Returns an error if the bytes are not valid UTF-8, or are not a valid
+    /// block.
+    #[inline]
+    pub const fn try_from_bytes(bytes: &[u8]) -> Result<&DStrBlock, FromBytesError> {
+        let mut start = 0;
+
+        // Validate one string at a time, in a single pass.
+        loop {
+            match mem::scan_until_nul(bytes, start) {
+                Ok(Some(nul_pos)) if nul_pos == start => {
+                    return if nul_pos + 1 == bytes.len() {
+                        // SAFETY: Every string up to here was valid UTF-8, and
+                        //         the first empty string is at the very end.
+                        Ok(unsafe { DStrBlock::from_bytes_unchecked(bytes) })
+                    } else {
+                        Err(FromBytesError::InteriorNul(nul_pos))
+                    };
+                }
+                Ok(Some(nul_pos)) => start = nul_pos + 1,
+                Ok(None) => return Err(FromBytesError::NotNulTerminated),
+                Err(_) => return Err(FromBytesError::InvalidUtf8(mem::utf8_error(bytes))),
+            }
+        }
+    }
+
+    /// Create a [`DStrBlock`] from a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes are not valid UTF-8, or are not a valid block.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_bytes(bytes: &[u8]) -> &DStrBlock {
+        match DStrBlock::try_from_bytes(bytes) {
+            Ok(block) => block,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to create a [`DStrBlock`] from a raw pointer, scanning for the
+    /// terminating empty string.
+    ///
+    /// Returns an error if the block is not valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the following:
+    ///
+    /// - `ptr` is not null, and points to a block terminated by an empty
+    ///   string.
+    ///
+    /// - The end of the block is within [`isize::MAX`] bytes from `ptr`.
+    ///
+    /// - The block is not mutated or deallocated for the lifetime `'a`.
+    pub const unsafe fn from_ptr<'a>(ptr: *const c_char) -> Result<&'a DStrBlock, FromBytesError> {
+        let mut len = 0;
+
+        loop {
+            // SAFETY: The caller ensures every string in the block is
+            //         nul-terminated.
+            let string_len = unsafe { mem::find_nul(ptr.add(len)) };
+
+            len += string_len + 1;
+
+            if string_len == 0 {
+                break;
+            }
+        }
+
+        // SAFETY: The block is `len` bytes long, including the terminator.
+        DStrBlock::try_from_bytes(unsafe { core::slice::from_raw_parts(ptr.cast::<u8>(), len) })
+    }
+
+    /// Returns the underlying string, including every nul terminator and the
+    /// terminating empty string.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the underlying bytes, including every nul terminator and the
+    /// terminating empty string.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.raw.as_bytes()
+    }
+
+    /// Returns a raw C pointer to the start of this [`DStrBlock`].
+    #[inline]
+    #[must_use]
+    pub const fn as_c_ptr(&self) -> *const c_char {
+        self.raw.as_ptr().cast()
+    }
+
+    /// Returns the strings of this [`DStrBlock`] as a [`DStrList`], without
+    /// the terminating empty string.
+    #[inline]
+    #[must_use]
+    pub const fn as_list(&self) -> &DStrList {
+        let bytes = self.as_bytes();
+
+        // SAFETY: A block always ends with an extra nul, and the rest is either
+        //         empty or ends with a nul.
+        unsafe {
+            let list = core::slice::from_raw_parts(bytes.as_ptr(), bytes.len() - 1);
+            DStrList::from_bytes_unchecked(list)
+        }
+    }
+
+    /// Returns whether this [`DStrBlock`] contains no strings.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.raw.len() == 1
+    }
+
+    /// Returns the amount of strings in this [`DStrBlock`], excluding the
+    /// terminating empty string.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.as_list().len()
+    }
+
+    /// Returns an iterator over the strings of this [`DStrBlock`], excluding
+    /// the terminating empty string.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        self.as_list().iter()
+    }
+}
+
+impl fmt::Debug for DStrBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IntoIterator for &'a DStrBlock {
+    type Item = &'a DStr;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl AsRef<DStrList> for DStrBlock {
+    #[inline]
+    fn as_ref(&self) -> &DStrList {
+        self.as_list()
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a DStrBlock {
+    type Error = FromBytesError;
+
+    #[inline]
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        DStrBlock::try_from_str(value)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for &'a DStrBlock {
+    type Error = FromBytesError;
+
+    #[inline]
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        DStrBlock::try_from_bytes(value)
+    }
+}
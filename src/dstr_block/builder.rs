@@ -0,0 +1,148 @@
+use core::{fmt, ops::Deref};
+
+use alloc::{boxed::Box, string::String};
+
+use crate::{dstr::NulError, DStr, DStrBlock};
+
+use super::BlockPushError;
+
+/// An owned, growable [`DStrBlock`].
+///
+/// The buffer always holds a valid block, so it can be passed on at any time
+/// through [`Deref`].
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DStrBlockBuilder {
+    buf: String,
+}
+
+impl DStrBlockBuilder {
+    /// Creates a new, empty [`DStrBlockBuilder`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> DStrBlockBuilder {
+        DStrBlockBuilder::with_capacity(0)
+    }
+
+    /// Creates a new, empty [`DStrBlockBuilder`] with room for at least
+    /// `capacity` bytes of strings, including their nul terminators.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> DStrBlockBuilder {
+        let mut buf = String::with_capacity(capacity.saturating_add(1));
+        buf.push('\0');
+
+        DStrBlockBuilder { buf }
+    }
+
+    /// Appends `string` to the end of the block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` is empty or contains a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, string: &str) {
+        if let Err(err) = self.try_push(string) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `string` to the end of the block.
+    ///
+    /// Returns an error if `string` is empty, since that would terminate the
+    /// block early, or if it contains a nul.
+    #[inline]
+    pub fn try_push(&mut self, string: &str) -> Result<(), BlockPushError> {
+        if string.is_empty() {
+            return Err(BlockPushError::EmptyString);
+        }
+
+        NulError::check(string.as_bytes())?;
+
+        self.buf.pop();
+        self.buf.push_str(string);
+        self.buf.push_str("\0\0");
+
+        Ok(())
+    }
+
+    /// Try to append `string` to the end of the block.
+    ///
+    /// Returns an error if `string` is empty.
+    #[inline]
+    pub fn try_push_dstr(&mut self, string: &DStr) -> Result<(), BlockPushError> {
+        if string.is_empty() {
+            return Err(BlockPushError::EmptyString);
+        }
+
+        self.buf.pop();
+        self.buf.push_str(string.as_str_with_nul());
+        self.buf.push('\0');
+
+        Ok(())
+    }
+
+    /// Removes every string from the block, keeping the capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.buf.push('\0');
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_block(&self) -> &DStrBlock {
+        // SAFETY: The buffer is always a valid block.
+        unsafe { DStrBlock::from_str_unchecked(&self.buf) }
+    }
+
+    /// Consumes this [`DStrBlockBuilder`], returning the block as a
+    /// [`String`].
+    #[inline]
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.buf
+    }
+
+    /// Consumes this [`DStrBlockBuilder`], returning the block as a boxed
+    /// [`DStrBlock`].
+    #[inline]
+    #[must_use]
+    pub fn into_boxed_block(self) -> Box<DStrBlock> {
+        let raw = Box::into_raw(self.buf.into_boxed_str());
+
+        // SAFETY: `DStrBlock` is a transparent wrapper around `str`, and the
+        //         buffer is a valid block.
+        unsafe { Box::from_raw(raw as *mut DStrBlock) }
+    }
+}
+
+impl Default for DStrBlockBuilder {
+    #[inline]
+    fn default() -> Self {
+        DStrBlockBuilder::new()
+    }
+}
+
+impl fmt::Debug for DStrBlockBuilder {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_block(), f)
+    }
+}
+
+impl Deref for DStrBlockBuilder {
+    type Target = DStrBlock;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_block()
+    }
+}
+
+impl From<DStrBlockBuilder> for Box<DStrBlock> {
+    #[inline]
+    fn from(value: DStrBlockBuilder) -> Self {
+        value.into_boxed_block()
+    }
+}
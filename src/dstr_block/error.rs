@@ -0,0 +1,68 @@
+use core::fmt;
+
+use crate::dstr::{msg, DStrError, ErrorKind, NulError};
+
+/// An error indicating that a string could not be added to a [`DStrBlock`].
+///
+/// [`DStrBlock`]: crate::DStrBlock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockPushError {
+    ContainsNul(usize),
+    /// An empty string would terminate the block early.
+    EmptyString,
+}
+
+impl BlockPushError {
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            BlockPushError::ContainsNul(_) => msg!(ContainsNul),
+            BlockPushError::EmptyString => msg!(EmptyString),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for BlockPushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        if let &Self::ContainsNul(pos) = self {
+            core::write!(f, " at pos {pos}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for BlockPushError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<NulError> for BlockPushError {
+    #[inline]
+    fn from(value: NulError) -> Self {
+        BlockPushError::ContainsNul(value.nul_position())
+    }
+}
+
+impl From<BlockPushError> for DStrError {
+    #[inline]
+    fn from(value: BlockPushError) -> Self {
+        match value {
+            BlockPushError::ContainsNul(nul_pos) => {
+                DStrError::new(ErrorKind::ContainsNul, Some(nul_pos))
+            }
+            BlockPushError::EmptyString => DStrError::new(ErrorKind::EmptyString, None),
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Module for interning nul-terminated UTF-8 strings.
+
+use core::{borrow::Borrow, cmp::Ordering, fmt, iter::FusedIterator, num::NonZeroU32, slice};
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{dstr::NulError, DStr, DStrArena};
+
+/// A handle to a string stored in a [`DStrInterner`].
+///
+/// Symbols are only meaningful for the interner that created them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Symbol(NonZeroU32);
+
+impl Symbol {
+    /// Returns the index of this [`Symbol`], which is the order in which its
+    /// string was interned, starting at zero.
+    #[inline]
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0.get() as usize - 1
+    }
+
+    /// Create a [`Symbol`] from an index returned by [`Symbol::index`].
+    ///
+    /// Returns `None` if the index is too large to be a symbol.
+    #[inline]
+    #[must_use]
+    pub const fn from_index(index: usize) -> Option<Symbol> {
+        if index >= u32::MAX as usize {
+            return None;
+        }
+
+        match NonZeroU32::new(index as u32 + 1) {
+            Some(symbol) => Some(Symbol(symbol)),
+            None => None,
+        }
+    }
+}
+
+/// A pointer to an interned string, which is ordered by the string.
+#[derive(Clone, Copy)]
+struct Key(*const DStr);
+
+impl Key {
+    #[inline]
+    fn get(&self) -> &DStr {
+        // SAFETY: Keys always point into the arena of their interner, which is
+        //         never reset while it lives.
+        unsafe { &*self.0 }
+    }
+}
+
+impl PartialEq for Key {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(other.get())
+    }
+}
+
+impl Borrow<DStr> for Key {
+    #[inline]
+    fn borrow(&self) -> &DStr {
+        self.get()
+    }
+}
+
+/// Since nul is the smallest character, and neither has interior nuls, a
+/// [`DStr`] is ordered the same as its [`str`].
+impl Borrow<str> for Key {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.get().as_str()
+    }
+}
+
+/// A string interner, which stores each distinct string once.
+///
+/// Strings are copied into a [`DStrArena`], so interning does not allocate for
+/// every string, and references to interned strings stay at the
+/// same address for as long as the interner lives. Each string is identified
+/// by a [`Symbol`], which is a small, copyable integer.
+#[derive(Default)]
+pub struct DStrInterner {
+    /// The storage of the strings, which is never reset.
+    arena: DStrArena,
+    /// Every interned string, indexed by symbol.
+    strings: Vec<Key>,
+    /// The symbol of every interned string.
+    symbols: BTreeMap<Key, Symbol>,
+}
+
+// SAFETY: The pointers only point into the owned arena.
+unsafe impl Send for DStrInterner {}
+
+// SAFETY: The pointers only point into the owned arena, which is only used
+//         through a mutable reference.
+unsafe impl Sync for DStrInterner {}
+
+impl DStrInterner {
+    /// Creates a new, empty [`DStrInterner`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> DStrInterner {
+        DStrInterner {
+            arena: DStrArena::new(),
+            strings: Vec::new(),
+            symbols: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the symbol of `string`, interning it if it was not already.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner would contain more than `u32::MAX - 1` strings.
+    #[inline]
+    #[track_caller]
+    pub fn get_or_intern(&mut self, string: &DStr) -> Symbol {
+        // SAFETY: A `DStr` contains no interior nuls.
+        unsafe { self.get_or_intern_unchecked(string.as_str()) }
+    }
+
+    /// Returns the symbol of `string`, interning it if it was not already.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul, or the interner would contain more
+    /// than `u32::MAX - 1` strings.
+    #[inline]
+    #[track_caller]
+    pub fn get_or_intern_str(&mut self, string: &str) -> Symbol {
+        match self.try_get_or_intern_str(string) {
+            Ok(symbol) => symbol,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to get the symbol of `string`, interning it if it was not already.
+    ///
+    /// Returns an error if `string` contains a nul.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interner would contain more than `u32::MAX - 1` strings.
+    #[track_caller]
+    pub fn try_get_or_intern_str(&mut self, string: &str) -> Result<Symbol, NulError> {
+        NulError::check(string.as_bytes())?;
+
+        // SAFETY: We just checked that `string` contains no nuls.
+        Ok(unsafe { self.get_or_intern_unchecked(string) })
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that `string` contains no nuls.
+    #[track_caller]
+    unsafe fn get_or_intern_unchecked(&mut self, string: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(string) {
+            return symbol;
+        }
+
+        let symbol = Symbol::from_index(self.strings.len()).expect("too many interned strings");
+        // SAFETY: The caller ensures `string` contains no nuls.
+        let key = Key(unsafe { self.arena.alloc_str_unchecked(string) });
+
+        self.strings.push(key);
+        self.symbols.insert(key, symbol);
+
+        symbol
+    }
+
+    /// Interns `string`, returning a reference to the interned copy.
+    #[inline]
+    #[track_caller]
+    pub fn intern(&mut self, string: &DStr) -> &DStr {
+        let symbol = self.get_or_intern(string);
+
+        self.strings[symbol.index()].get()
+    }
+
+    /// Returns the symbol of `string`, or `None` if it was not interned.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, string: &DStr) -> Option<Symbol> {
+        self.symbols.get(string).copied()
+    }
+
+    /// Returns the string of `symbol`, or `None` if it is not from this
+    /// interner.
+    #[inline]
+    #[must_use]
+    pub fn resolve(&self, symbol: Symbol) -> Option<&DStr> {
+        self.strings.get(symbol.index()).map(Key::get)
+    }
+
+    /// Returns the amount of interned strings.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns whether no strings are interned.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns an iterator over the interned strings and their symbols, in
+    /// the order they were interned.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.strings.iter().enumerate(),
+        }
+    }
+}
+
+impl fmt::Debug for DStrInterner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IntoIterator for &'a DStrInterner {
+    type Item = (Symbol, &'a DStr);
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the strings in a [`DStrInterner`].
+///
+/// This is created by [`DStrInterner::iter`].
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a> {
+    inner: core::iter::Enumerate<slice::Iter<'a, Key>>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (Symbol, &'a DStr);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, key) = self.inner.next()?;
+
+        // SAFETY: Every index in the interner is a valid symbol.
+        let symbol = Symbol(unsafe { NonZeroU32::new_unchecked(index as u32 + 1) });
+
+        Some((symbol, key.get()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+impl fmt::Debug for Iter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
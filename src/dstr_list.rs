@@ -0,0 +1,284 @@
+//! Module for lists of consecutive nul-terminated UTF-8 strings.
+
+use core::{fmt, iter::FusedIterator, ops::Index, str::from_utf8_unchecked};
+
+use crate::{dstr::FromBytesError, mem, DStr};
+
+/// A list of consecutive nul-terminated UTF-8 strings, such as the contents of
+/// `/proc/self/cmdline` or the output of `git ls-files -z`.
+///
+/// Every string in the list is terminated by a nul, including the last one,
+/// and an empty buffer is an empty list.
+///
+/// Since the strings are not indexed, [`DStrList::len`] and
+/// [`DStrList::get`] scan the list.
+///
+/// # Safety
+///
+/// - The internal representation must always be valid UTF-8.
+///
+/// - The buffer is either empty, or the last byte is a nul.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct DStrList {
+    raw: str,
+}
+
+impl DStrList {
+    /// An empty [`DStrList`].
+    pub const EMPTY: &'static DStrList = DStrList::from_str("");
+
+    /// Create a [`DStrList`] from a string without doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `string` is either empty, or ends with a
+    /// nul.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn from_str_unchecked(string: &str) -> &DStrList {
+        // SAFETY: `DStrList` is a transparent wrapper around `str`, and the
+        //         caller ensures the string is empty or ends with a nul.
+        unsafe { &*(string as *const str as *const DStrList) }
+    }
+
+    /// Create a [`DStrList`] from a byte slice without doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` is valid UTF-8, and is either empty,
+    /// or ends with a nul.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &DStrList {
+        // SAFETY: The caller ensures the bytes are valid UTF-8, and are empty
+        //         or end with a nul.
+        unsafe { DStrList::from_str_unchecked(from_utf8_unchecked(bytes)) }
+    }
+
+    /// Try to create a [`DStrList`] from a string.
+    ///
+    /// Returns an error if the string is not empty and does not end with a
+    /// nul.
+    #[inline]
+    pub const fn try_from_str(string: &str) -> Result<&DStrList, FromBytesError> {
+        match string.as_bytes() {
+            // SAFETY: We just checked that the string is empty or ends with a
+            //         nul.
+            [] | [.., 0] => Ok(unsafe { DStrList::from_str_unchecked(string) }),
+            _ => Err(FromBytesError::NotNulTerminated),
+        }
+    }
+
+    /// Create a [`DStrList`] from a string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not empty and does not end with a nul.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_str(string: &str) -> &DStrList {
+        match DStrList::try_from_str(string) {
+            Ok(list) => list,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to create a [`DStrList`] from a byte slice.
+    ///
+    /// Returns an error if the bytes are not valid UTF-8, or are not empty and
+    /// do not end with a nul.
+    #[inline]
+    pub const fn try_from_bytes(bytes: &[u8]) -> Result<&DStrList, FromBytesError> {
+        match core::str::from_utf8(bytes) {
+            Ok(string) => DStrList::try_from_str(string),
+            Err(err) => Err(FromBytesError::InvalidUtf8(err)),
+        }
+    }
+
+    /// Create a [`DStrList`] from a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes are not valid UTF-8, or are not empty and do not
+    /// end with a nul.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_bytes(bytes: &[u8]) -> &DStrList {
+        match DStrList::try_from_bytes(bytes) {
+            Ok(list) => list,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Returns the underlying string, including every nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the underlying bytes, including every nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.raw.as_bytes()
+    }
+
+    /// Returns whether this [`DStrList`] contains no strings.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Returns the amount of strings in this [`DStrList`].
+    ///
+    /// This counts the nuls in the list.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.as_bytes().iter().filter(|&&byte| byte == 0).count()
+    }
+
+    /// Returns the string at `index`, or `None` if it is out of bounds.
+    ///
+    /// This scans the list up to the string.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&DStr> {
+        self.iter().nth(index)
+    }
+
+    /// Returns the first string in this [`DStrList`].
+    #[inline]
+    #[must_use]
+    pub fn first(&self) -> Option<&DStr> {
+        self.iter().next()
+    }
+
+    /// Returns the last string in this [`DStrList`].
+    #[inline]
+    #[must_use]
+    pub fn last(&self) -> Option<&DStr> {
+        self.iter().next_back()
+    }
+
+    /// Returns an iterator over the strings in this [`DStrList`].
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { rest: &self.raw }
+    }
+}
+
+impl fmt::Debug for DStrList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for DStrList {
+    type Output = DStr;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(string) => string,
+            None => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a DStrList {
+    type Item = &'a DStr;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a DStrList {
+    type Error = FromBytesError;
+
+    #[inline]
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        DStrList::try_from_str(value)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for &'a DStrList {
+    type Error = FromBytesError;
+
+    #[inline]
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        DStrList::try_from_bytes(value)
+    }
+}
+
+/// An iterator over the strings in a [`DStrList`].
+///
+/// This is created by [`DStrList::iter`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a> {
+    /// The remaining strings, which is either empty or ends with a nul.
+    rest: &'a str,
+}
+
+impl<'a> Iter<'a> {
+    /// Returns the remaining strings as a [`DStrList`].
+    #[inline]
+    #[must_use]
+    pub fn as_list(&self) -> &'a DStrList {
+        // SAFETY: The remainder is always a valid list.
+        unsafe { DStrList::from_str_unchecked(self.rest) }
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a DStr;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let nul = mem::find_byte(0, self.rest.as_bytes())?;
+        let (string, rest) = self.rest.split_at(nul + 1);
+
+        self.rest = rest;
+
+        // SAFETY: `string` ends at the first nul.
+        Some(unsafe { DStr::from_str_with_nul_unchecked(string) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest.len();
+
+        (len.min(1), Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (_, init) = self.rest.as_bytes().split_last()?;
+        let start = init
+            .iter()
+            .rposition(|&byte| byte == 0)
+            .map_or(0, |nul| nul + 1);
+
+        let (rest, string) = self.rest.split_at(start);
+
+        self.rest = rest;
+
+        // SAFETY: `string` ends at the last nul, and contains no other nuls.
+        Some(unsafe { DStr::from_str_with_nul_unchecked(string) })
+    }
+}
+
+impl FusedIterator for Iter<'_> {}
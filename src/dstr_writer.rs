@@ -0,0 +1,189 @@
+//! Module for formatting nul-terminated UTF-8 strings into borrowed buffers.
+
+use core::{fmt, mem::MaybeUninit, ops::Deref};
+
+use crate::{
+    dstr::{NulError, PushError},
+    DStr,
+};
+
+/// A writer that builds a nul-terminated UTF-8 string in a borrowed buffer,
+/// such as one on the stack.
+///
+/// The buffer includes the nul terminator, which is always kept in place, so
+/// the string can be handed to C at any point. Like a [`DArrayString`], this
+/// never allocates, and writes that do not fit fail without writing anything.
+///
+/// [`DArrayString`]: crate::DArrayString
+pub struct DStrWriter<'a> {
+    /// The buffer, of which the first `len + 1` bytes are initialized.
+    buf: &'a mut [MaybeUninit<u8>],
+    len: usize,
+}
+
+impl<'a> DStrWriter<'a> {
+    /// Creates a new [`DStrWriter`] writing into `buf`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is empty, as there would be no room for the nul
+    /// terminator.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn new(buf: &'a mut [u8]) -> DStrWriter<'a> {
+        // SAFETY: Only initialized bytes are ever written to the buffer.
+        let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+
+        DStrWriter::new_uninit(buf)
+    }
+
+    /// Creates a new [`DStrWriter`] writing into the possibly uninitialized
+    /// `buf`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is empty, as there would be no room for the nul
+    /// terminator.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn new_uninit(buf: &'a mut [MaybeUninit<u8>]) -> DStrWriter<'a> {
+        assert!(
+            !buf.is_empty(),
+            "a `DStrWriter` needs room for the nul terminator"
+        );
+
+        buf[0] = MaybeUninit::new(0);
+
+        DStrWriter { buf, len: 0 }
+    }
+
+    /// Returns the capacity of this [`DStrWriter`] in bytes, excluding the
+    /// byte reserved for the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.buf.len() - 1
+    }
+
+    /// Returns how many more bytes fit in this [`DStrWriter`].
+    #[inline]
+    #[must_use]
+    pub const fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len
+    }
+
+    /// Returns whether this [`DStrWriter`] is at full capacity.
+    #[inline]
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Appends `string` to the end of the buffer, before the nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul or does not fit.
+    #[inline]
+    #[track_caller]
+    pub fn push_str(&mut self, string: &str) {
+        if let Err(err) = self.try_push_str(string) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `string` to the end of the buffer, before the nul
+    /// terminator.
+    ///
+    /// Returns an error if `string` contains a nul or does not fit, in which
+    /// case nothing is appended.
+    #[inline]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), PushError> {
+        if string.len() > self.remaining_capacity() {
+            return Err(PushError::CapacityExceeded);
+        }
+
+        NulError::check(string.as_bytes())?;
+
+        let end = self.len + string.len();
+
+        // SAFETY: `end` is smaller than the length of the buffer, and the
+        //         source is a different allocation.
+        unsafe {
+            let dst = self.buf.as_mut_ptr().add(self.len).cast::<u8>();
+
+            dst.copy_from_nonoverlapping(string.as_ptr(), string.len());
+            dst.add(string.len()).write(0);
+        }
+
+        self.len = end;
+
+        Ok(())
+    }
+
+    /// Try to append `ch` to the end of the buffer, before the nul terminator.
+    ///
+    /// Returns an error if `ch` is a nul or does not fit.
+    #[inline]
+    pub fn try_push(&mut self, ch: char) -> Result<(), PushError> {
+        self.try_push_str(ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Removes everything written so far, leaving only the nul terminator.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf[0] = MaybeUninit::new(0);
+        self.len = 0;
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn as_dstr(&self) -> &DStr {
+        // SAFETY: The first `len + 1` bytes are always initialized, and are
+        //         valid UTF-8 without nuls, followed by the nul terminator.
+        unsafe {
+            let bytes = core::slice::from_raw_parts(self.buf.as_ptr().cast::<u8>(), self.len + 1);
+            DStr::from_bytes_with_nul_unchecked(bytes)
+        }
+    }
+
+    /// Consumes this [`DStrWriter`], returning the string written to the
+    /// buffer.
+    #[inline]
+    #[must_use]
+    pub fn finish(self) -> &'a DStr {
+        // SAFETY: The first `len + 1` bytes are always initialized, and are
+        //         valid UTF-8 without nuls, followed by the nul terminator.
+        unsafe {
+            let bytes = core::slice::from_raw_parts(self.buf.as_ptr().cast::<u8>(), self.len + 1);
+            DStr::from_bytes_with_nul_unchecked(bytes)
+        }
+    }
+}
+
+impl fmt::Write for DStrWriter<'_> {
+    /// Fails if `s` contains a nul or does not fit, in which case nothing is
+    /// appended.
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl fmt::Debug for DStrWriter<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dstr(), f)
+    }
+}
+
+impl Deref for DStrWriter<'_> {
+    type Target = DStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_dstr()
+    }
+}
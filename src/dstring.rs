@@ -1,32 +1,1131 @@
-use core::ops::{Deref, DerefMut};
+//! Module for owned nul-terminated UTF-8 strings.
 
-use alloc::{borrow::ToOwned, string::String};
+#[cfg(not(feature = "allocator_api"))]
+use core::marker::PhantomData;
+use core::{
+    cmp::Ordering,
+    ffi::CStr,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign, Bound, Deref, DerefMut, Range, RangeBounds},
+    str::{from_utf8_unchecked, Utf8Error},
+};
 
-use crate::DStr;
+use alloc::{collections::TryReserveError, string::String, vec::Vec};
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+use crate::{
+    dstr::{EditError, FromBytesError, IndexError, NulError, PushError},
+    DStr,
+};
+
+#[cfg(feature = "allocator_api")]
+pub use alloc::alloc::{Allocator, Global};
+
+#[cfg(not(feature = "allocator_api"))]
+mod allocator;
+#[cfg(not(feature = "allocator_api"))]
+pub use allocator::*;
+
+mod drain;
+pub use drain::*;
+
+mod edit;
+pub use edit::*;
+
+#[cfg(feature = "hashbrown")]
+mod equivalent;
+
+mod join;
+pub use join::*;
+
+#[cfg(feature = "std")]
+mod os;
+#[cfg(feature = "std")]
+pub use os::*;
+
+/// Creates a [`DString`] using interpolation of runtime expressions.
+///
+/// This behaves the same as [`format!`], see [`DString::from_fmt`].
+///
+/// # Panics
+///
+/// Panics if the output contains a nul, or if a formatting trait
+/// implementation returns an error.
+///
+/// [`format!`]: alloc::format
+#[macro_export]
+macro_rules! dformat {
+    ($($arg:tt)*) => {
+        $crate::DString::from_fmt(::core::format_args!($($arg)*))
+    };
+}
+
+/// An owned nul-terminated UTF-8 string.
+///
+/// This is to [`DStr`] what [`String`] is to [`str`]. The nul terminator is
+/// always stored, so the underlying buffer is never empty, and the capacity
+/// reported by [`DString::capacity`] excludes the terminator.
+///
+/// With the `allocator_api` feature, a [`DString`] can be stored in any
+/// [`Allocator`], the same way as a [`Vec`].
+///
+/// # Avoiding panics
+///
+/// The `try_` methods that grow the string still panic if the capacity
+/// overflows, unless they are about the capacity itself. Code that must never
+/// panic can stick to [`DString::try_with_capacity`], [`DString::try_reserve`],
+/// [`DString::try_reserve_exact`], [`DString::push_within_capacity`] and
+/// [`DString::push_str_within_capacity`].
+///
+/// Indices are checked by [`DString::try_insert`],
+/// [`DString::try_insert_str`], [`DString::try_replace_range`],
+/// [`DString::try_remove`], [`DString::try_truncate`],
+/// [`DString::try_split_off`] and [`DString::try_drain`], which return an
+/// error for an index that is out of bounds or not on a char boundary. Use
+/// [`DStr::get_suffix`] and [`str::get`] on [`DStr::as_str`] for indexing.
+///
+/// Running out of memory aborts rather than panics, the same as for a
+/// [`String`]. See also the subsets of [`DArrayString`], which never
+/// allocates, and [`DSmallString`].
+///
+/// [`DArrayString`]: crate::DArrayString
+/// [`DSmallString`]: crate::DSmallString
 #[repr(transparent)]
-pub struct DString {
+pub struct DString<A: Allocator = Global> {
+    #[cfg(feature = "allocator_api")]
+    inner: Vec<u8, A>,
+    #[cfg(not(feature = "allocator_api"))]
     inner: String,
+    #[cfg(not(feature = "allocator_api"))]
+    alloc: PhantomData<A>,
 }
 
 impl DString {
+    /// Creates a new empty [`DString`].
+    ///
+    /// Since the nul terminator always needs to be stored, this allocates.
+    #[inline]
+    #[must_use]
+    pub fn new() -> DString {
+        DString::with_capacity(0)
+    }
+
+    /// Creates a new empty [`DString`] with room for at least `capacity` bytes,
+    /// excluding the nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity overflows.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> DString {
+        let capacity = capacity.checked_add(1).expect("capacity overflow");
+
+        let mut inner = Vec::with_capacity(capacity);
+        inner.push(0);
+
+        // SAFETY: The buffer only contains the nul terminator.
+        unsafe { DString::from_buf_unchecked(inner) }
+    }
+
+    /// Try to create a new empty [`DString`] with room for at least
+    /// `capacity` bytes, excluding the nul terminator.
+    ///
+    /// Returns an error if the capacity overflows or the allocation fails.
+    /// This never panics.
+    #[inline]
+    pub fn try_with_capacity(capacity: usize) -> Result<DString, TryReserveError> {
+        let mut inner = Vec::new();
+        // A saturated capacity is larger than `isize::MAX`, which fails.
+        inner.try_reserve(capacity.saturating_add(1))?;
+        inner.push(0);
+
+        // SAFETY: The buffer only contains the nul terminator.
+        Ok(unsafe { DString::from_buf_unchecked(inner) })
+    }
+
+    /// Creates a new [`DString`] from formatting `args`.
+    ///
+    /// This is usually called through [`format_args!`]. When `args` has no
+    /// arguments to format, the result is built with a single allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output contains a nul, or if a formatting trait
+    /// implementation returns an error.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn from_fmt(args: fmt::Arguments<'_>) -> DString {
+        match DString::try_from_fmt(args) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to create a new [`DString`] from formatting `args`.
+    ///
+    /// Returns an error if the output contains a nul, in which case the
+    /// position is relative to the start of the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a formatting trait implementation returns an error.
+    pub fn try_from_fmt(args: fmt::Arguments<'_>) -> Result<DString, NulError> {
+        if let Some(string) = args.as_str() {
+            NulError::check(string.as_bytes())?;
+
+            let mut result = DString::with_capacity(string.len());
+
+            // SAFETY: We just checked that `string` contains no nuls.
+            unsafe { result.push_str_unchecked(string) };
+
+            return Ok(result);
+        }
+
+        let mut writer = FmtWriter {
+            string: DString::new(),
+            error: None,
+        };
+
+        match fmt::write(&mut writer, args) {
+            Ok(()) => Ok(writer.string),
+            Err(_) => match writer.error {
+                Some(err) => Err(err),
+                None => panic!(
+                    "a formatting trait implementation returned an error when the underlying stream did not"
+                ),
+            },
+        }
+    }
+
+    /// Create a [`DString`] from a nul-terminated [`String`] without
+    /// doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the input is a nul-terminated string
+    /// with no interior nuls.
+    #[inline]
+    #[must_use]
+    pub unsafe fn from_string_with_nul_unchecked(string: String) -> DString {
+        debug_assert!(
+            string.ends_with('\0'),
+            "a nul-terminated string must end with a nul"
+        );
+
+        // SAFETY: The caller upholds the invariants.
+        unsafe { DString::from_buf_unchecked(string.into_bytes()) }
+    }
+
+    /// Returns a guard giving access to the underlying [`String`], without the
+    /// nul terminator.
+    ///
+    /// This makes the entire [`String`] API available. The nul terminator is
+    /// put back once the guard is dropped, see [`EditGuard`] for what happens
+    /// if a nul was added.
+    #[inline]
+    pub fn edit(&mut self) -> EditGuard<'_> {
+        EditGuard::new(self.buf_mut())
+    }
+
+    /// Converts this [`DString`] into its underlying buffer, including the nul
+    /// terminator.
+    #[inline]
+    #[must_use]
+    pub(crate) fn into_bytes_with_nul(self) -> Vec<u8> {
+        #[cfg(feature = "allocator_api")]
+        {
+            self.inner
+        }
+
+        #[cfg(not(feature = "allocator_api"))]
+        {
+            self.inner.into_bytes()
+        }
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<A: Allocator> DString<A> {
+    /// Creates a new empty [`DString`] in the given allocator.
+    ///
+    /// Since the nul terminator always needs to be stored, this allocates.
+    #[inline]
+    #[must_use]
+    pub fn new_in(alloc: A) -> DString<A> {
+        DString::with_capacity_in(0, alloc)
+    }
+
+    /// Creates a new empty [`DString`] in the given allocator, with room for at
+    /// least `capacity` bytes, excluding the nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity overflows.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> DString<A> {
+        let capacity = capacity.checked_add(1).expect("capacity overflow");
+
+        let mut inner = Vec::with_capacity_in(capacity, alloc);
+        inner.push(0);
+
+        // SAFETY: The buffer only contains the nul terminator.
+        unsafe { DString::from_buf_unchecked(inner) }
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    #[must_use]
+    pub fn allocator(&self) -> &A {
+        self.inner.allocator()
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that `inner` is nul-terminated UTF-8 with no
+    /// interior nuls.
+    #[inline]
+    const unsafe fn from_buf_unchecked(inner: Vec<u8, A>) -> DString<A> {
+        DString { inner }
+    }
+
+    /// Returns a mutable reference to the underlying buffer as a
+    /// [`Vec<u8, A>`], including the nul terminator.
+    ///
+    /// This behaves the same as [`String::as_mut_vec`].
+    ///
+    /// # Safety
+    ///
+    /// Before the borrow ends the caller must ensure the following:
+    ///
+    /// - The content of the buffer is valid UTF-8.
+    ///
+    /// - The buffer contains no interior nuls.
+    ///
+    /// - The buffer is nul-terminated.
+    #[inline]
+    #[must_use]
+    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8, A> {
+        self.buf_mut()
+    }
+
+    /// Returns the underlying buffer, including the nul terminator.
+    ///
+    /// Every caller leaves the buffer nul-terminated UTF-8 without interior
+    /// nuls.
+    #[inline]
+    fn buf_mut(&mut self) -> &mut Vec<u8, A> {
+        &mut self.inner
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
+impl<A: Allocator> DString<A> {
+    /// # Safety
+    ///
+    /// The caller must ensure that `inner` is nul-terminated UTF-8 with no
+    /// interior nuls.
+    #[inline]
+    unsafe fn from_buf_unchecked(inner: Vec<u8>) -> DString<A> {
+        DString {
+            // SAFETY: The caller ensures `inner` is valid UTF-8.
+            inner: unsafe { String::from_utf8_unchecked(inner) },
+            alloc: PhantomData,
+        }
+    }
+
+    /// Returns a mutable reference to the underlying buffer as a
+    /// [`Vec<u8>`], including the nul terminator.
+    ///
+    /// This behaves the same as [`String::as_mut_vec`].
+    ///
+    /// # Safety
+    ///
+    /// Before the borrow ends the caller must ensure the following:
+    ///
+    /// - The content of the buffer is valid UTF-8.
+    ///
+    /// - The buffer contains no interior nuls.
+    ///
+    /// - The buffer is nul-terminated.
+    #[inline]
+    #[must_use]
+    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+        self.buf_mut()
+    }
+
+    /// Returns the underlying buffer, including the nul terminator.
+    ///
+    /// Every caller leaves the buffer nul-terminated UTF-8 without interior
+    /// nuls, which keeps the [`String`] valid.
+    #[inline]
+    fn buf_mut(&mut self) -> &mut Vec<u8> {
+        // SAFETY: Every caller keeps the buffer valid UTF-8.
+        unsafe { self.inner.as_mut_vec() }
+    }
+}
+
+impl<A: Allocator> DString<A> {
+    /// Returns the capacity of this [`DString`] in bytes, excluding the
+    /// byte reserved for the nul terminator.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity() - 1
+    }
+
+    /// Reserves room for at least `additional` more bytes, excluding the
+    /// nul terminator, which is always accounted for.
+    ///
+    /// This behaves the same as [`String::reserve`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity overflows.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        // `inner` already holds the nul, so there is no need to account for it.
+        self.buf_mut().reserve(additional);
+    }
+
+    /// Reserves room for exactly `additional` more bytes, excluding the
+    /// nul terminator, which is always accounted for.
+    ///
+    /// This behaves the same as [`String::reserve_exact`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity overflows.
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.buf_mut().reserve_exact(additional);
+    }
+
+    /// Try to reserve room for at least `additional` more bytes, excluding
+    /// the nul terminator, which is always accounted for.
+    ///
+    /// This behaves the same as [`String::try_reserve`], and never panics.
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf_mut().try_reserve(additional)
+    }
+
+    /// Try to reserve room for exactly `additional` more bytes, excluding the
+    /// nul terminator, which is always accounted for.
+    ///
+    /// This behaves the same as [`String::try_reserve_exact`], and never
+    /// panics.
+    #[inline]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf_mut().try_reserve_exact(additional)
+    }
+
+    /// Shrinks the capacity of this [`DString`] to match its length, keeping
+    /// room for the nul terminator.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.buf_mut().shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of this [`DString`] to at least `min_capacity`
+    /// bytes, excluding the nul terminator.
+    ///
+    /// This behaves the same as [`String::shrink_to`].
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.buf_mut().shrink_to(min_capacity.saturating_add(1));
+    }
+
+    /// Appends `string` without checking for nuls.
+    ///
+    /// The old nul terminator is overwritten, and the string re-terminated.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `string` contains no nuls.
+    #[inline]
+    pub(crate) unsafe fn push_str_unchecked(&mut self, string: &str) {
+        self.buf_mut().reserve(string.len());
+
+        // We replace the nul with valid UTF-8 and then re-terminate it, and
+        // the caller ensures `string` contains no nuls.
+        self.buf_mut().pop();
+        self.buf_mut().extend_from_slice(string.as_bytes());
+        self.buf_mut().push(0);
+    }
+
+    /// Appends `ch` to the end of this [`DString`], before the nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, ch: char) {
+        if let Err(err) = self.try_push(ch) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `ch` to the end of this [`DString`], before the nul
+    /// terminator.
+    ///
+    /// Returns an error if `ch` is a nul.
+    #[inline]
+    pub fn try_push(&mut self, ch: char) -> Result<(), NulError> {
+        if ch == '\0' {
+            return Err(NulError::new(0));
+        }
+
+        // SAFETY: We just checked that `ch` is not a nul.
+        unsafe { self.push_str_unchecked(ch.encode_utf8(&mut [0; 4])) };
+
+        Ok(())
+    }
+
+    /// Appends `string` to the end of this [`DString`], before the nul
+    /// terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push_str(&mut self, string: &str) {
+        if let Err(err) = self.try_push_str(string) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `string` to the end of this [`DString`], before the nul
+    /// terminator.
+    ///
+    /// Returns an error if `string` contains a nul, in which case nothing is
+    /// appended.
+    #[inline]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), NulError> {
+        NulError::check(string.as_bytes())?;
+
+        // SAFETY: We just checked that `string` contains no nuls.
+        unsafe { self.push_str_unchecked(string) };
+
+        Ok(())
+    }
+
+    /// Appends `ch` to the end of this [`DString`] without growing its
+    /// capacity.
+    ///
+    /// Returns an error if `ch` is a nul or does not fit in the remaining
+    /// capacity. This never allocates or panics.
+    #[inline]
+    pub fn push_within_capacity(&mut self, ch: char) -> Result<(), PushError> {
+        self.push_str_within_capacity(ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Appends `string` to the end of this [`DString`] without growing its
+    /// capacity.
+    ///
+    /// Returns an error if `string` contains a nul or does not fit in the
+    /// remaining capacity, in which case nothing is appended. This never
+    /// allocates or panics.
+    #[inline]
+    pub fn push_str_within_capacity(&mut self, string: &str) -> Result<(), PushError> {
+        if string.len() > self.capacity() - self.len() {
+            return Err(PushError::CapacityExceeded);
+        }
+
+        NulError::check(string.as_bytes())?;
+
+        // SAFETY: We just checked that `string` contains no nuls, and it fits
+        //         so nothing is reallocated.
+        unsafe { self.push_str_unchecked(string) };
+
+        Ok(())
+    }
+
+    /// Inserts `ch` at the byte position `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is a nul, or if `idx` is larger than [`DStr::len`] or
+    /// does not lie on a char boundary.
+    #[inline]
+    #[track_caller]
+    pub fn insert(&mut self, idx: usize, ch: char) {
+        if let Err(err) = self.try_insert(idx, ch) {
+            err._panic()
+        }
+    }
+
+    /// Try to insert `ch` at the byte position `idx`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ch` is a nul, or if `idx` is larger than
+    /// [`DStr::len`] or does not lie on a char boundary, in which case
+    /// nothing is inserted.
+    #[inline]
+    pub fn try_insert(&mut self, idx: usize, ch: char) -> Result<(), EditError> {
+        self.try_insert_str(idx, ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Inserts `string` at the byte position `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul, or if `idx` is larger than
+    /// [`DStr::len`] or does not lie on a char boundary.
+    #[inline]
+    #[track_caller]
+    pub fn insert_str(&mut self, idx: usize, string: &str) {
+        if let Err(err) = self.try_insert_str(idx, string) {
+            err._panic()
+        }
+    }
+
+    /// Try to insert `string` at the byte position `idx`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `string` contains a nul, or if `idx` is larger
+    /// than [`DStr::len`] or does not lie on a char boundary, in which case
+    /// nothing is inserted.
+    #[inline]
+    pub fn try_insert_str(&mut self, idx: usize, string: &str) -> Result<(), EditError> {
+        IndexError::check(self.as_str(), idx)?;
+        NulError::check(string.as_bytes())?;
+
+        // `idx` is at most the position of the nul, so it stays at the end.
+        self.buf_mut().splice(idx..idx, string.bytes());
+
+        Ok(())
+    }
+
+    /// Removes the [`char`] at the byte position `idx` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is not smaller than [`DStr::len`], or does not lie on
+    /// a char boundary. The nul terminator can never be removed.
+    #[inline]
+    #[track_caller]
+    pub fn remove(&mut self, idx: usize) -> char {
+        match self.try_remove(idx) {
+            Ok(ch) => ch,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to remove the [`char`] at the byte position `idx` and return it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `idx` is not smaller than [`DStr::len`], or does
+    /// not lie on a char boundary, in which case nothing is removed.
+    #[inline]
+    pub fn try_remove(&mut self, idx: usize) -> Result<char, IndexError> {
+        IndexError::check(self.as_str(), idx)?;
+
+        // SAFETY: We just checked that `idx` lies on a char boundary.
+        let rest = unsafe { self.as_str().get_unchecked(idx..) };
+        let ch = match rest.chars().next() {
+            Some(ch) => ch,
+            None => return Err(IndexError::OutOfBounds(idx)),
+        };
+
+        self.buf_mut().drain(idx..idx + ch.len_utf8());
+
+        Ok(ch)
+    }
+
+    /// Removes the last [`char`] before the nul terminator and returns it.
+    ///
+    /// Returns `None` if this [`DString`] is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.last_char()?;
+        self.truncate(self.len() - ch.len_utf8());
+
+        Some(ch)
+    }
+
+    /// Shortens this [`DString`] to `new_len` bytes, excluding the nul
+    /// terminator, which is moved to the cut.
+    ///
+    /// If `new_len` is not smaller than [`DStr::len`], this does nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` does not lie on a char boundary.
+    #[inline]
+    #[track_caller]
+    pub fn truncate(&mut self, new_len: usize) {
+        if let Err(err) = self.try_truncate(new_len) {
+            err._panic()
+        }
+    }
+
+    /// Try to shorten this [`DString`] to `new_len` bytes, excluding the nul
+    /// terminator, which is moved to the cut.
+    ///
+    /// If `new_len` is not smaller than [`DStr::len`], this does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `new_len` does not lie on a char boundary, in
+    /// which case nothing is removed.
+    #[inline]
+    pub fn try_truncate(&mut self, new_len: usize) -> Result<(), IndexError> {
+        if new_len < self.len() {
+            IndexError::check(self.as_str(), new_len)?;
+
+            // This keeps the capacity, so pushing the nul never reallocates.
+            self.buf_mut().truncate(new_len);
+            self.buf_mut().push(0);
+        }
+
+        Ok(())
+    }
+
+    /// Splits this [`DString`] in two at the byte position `at`.
+    ///
+    /// Returns everything after `at`, while this [`DString`] keeps everything
+    /// before it. Both halves end up nul-terminated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is larger than [`DStr::len`] or does not lie on a char
+    /// boundary.
+    #[inline]
+    #[track_caller]
+    #[must_use = "use `.truncate()` if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> DString<A>
+    where
+        A: Clone,
+    {
+        match self.try_split_off(at) {
+            Ok(tail) => tail,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to split this [`DString`] in two at the byte position `at`.
+    ///
+    /// Returns everything after `at`, while this [`DString`] keeps everything
+    /// before it. Both halves end up nul-terminated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `at` is larger than [`DStr::len`] or does not lie
+    /// on a char boundary, in which case nothing is split off.
+    #[inline]
+    pub fn try_split_off(&mut self, at: usize) -> Result<DString<A>, IndexError>
+    where
+        A: Clone,
+    {
+        IndexError::check(self.as_str(), at)?;
+
+        // The nul terminator ends up in the tail, so re-terminate the head.
+        let tail = self.buf_mut().split_off(at);
+        self.buf_mut().push(0);
+
+        // SAFETY: `at` lies on a char boundary, and the tail keeps the nul.
+        Ok(unsafe { DString::from_buf_unchecked(tail) })
+    }
+
+    /// Retains only the [`char`]s for which `f` returns `true`, in place.
+    ///
+    /// This behaves the same as [`String::retain`], and the nul terminator is
+    /// never passed to `f`.
+    #[inline]
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, mut f: F) {
+        let len = self.len();
+
+        let mut guard = Retain {
+            string: self,
+            idx: 0,
+            deleted: 0,
+        };
+
+        while guard.idx < len {
+            // SAFETY: `idx` always lies on a char boundary before the nul.
+            let rest = unsafe { guard.string.as_str().get_unchecked(guard.idx..) };
+            let ch = match rest.chars().next() {
+                Some(ch) => ch,
+                None => unreachable!(),
+            };
+
+            let ch_len = ch.len_utf8();
+
+            if !f(ch) {
+                guard.deleted += ch_len;
+            } else if guard.deleted > 0 {
+                let start = guard.idx;
+                guard
+                    .string
+                    .buf_mut()
+                    .copy_within(start..start + ch_len, start - guard.deleted);
+            }
+
+            guard.idx += ch_len;
+        }
+    }
+
+    /// Removes every [`char`] from this [`DString`], leaving only the nul
+    /// terminator.
+    ///
+    /// This keeps the allocated capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf_mut().clear();
+        self.buf_mut().push(0);
+    }
+
+    /// Replaces the given byte range with `string`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul, or if the range is out of bounds or
+    /// its ends do not lie on char boundaries. The range is bounded by
+    /// [`DStr::len`], so the nul terminator can never be replaced.
+    #[inline]
+    #[track_caller]
+    pub fn replace_range<R: RangeBounds<usize>>(&mut self, range: R, string: &str) {
+        if let Err(err) = self.try_replace_range(range, string) {
+            err._panic()
+        }
+    }
+
+    /// Try to replace the given byte range with `string`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `string` contains a nul, or if the range is out of
+    /// bounds or its ends do not lie on char boundaries, in which case
+    /// nothing is replaced. The range is bounded by [`DStr::len`], so the nul
+    /// terminator can never be replaced.
+    #[inline]
+    pub fn try_replace_range<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+        string: &str,
+    ) -> Result<(), EditError> {
+        let range = self.try_resolve_range(range)?;
+
+        NulError::check(string.as_bytes())?;
+
+        self.buf_mut().splice(range, string.bytes());
+
+        Ok(())
+    }
+
+    /// Removes the given byte range, returning an iterator over the removed
+    /// [`char`]s.
+    ///
+    /// This behaves the same as [`String::drain`]. The range is removed once
+    /// the iterator is dropped, and the nul terminator is never part of it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its ends do not lie on char
+    /// boundaries. The range is bounded by [`DStr::len`].
+    #[inline]
+    #[track_caller]
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, A> {
+        let range = self.resolve_range(range);
+
+        Drain::new(self, range)
+    }
+
+    /// Try to remove the given byte range, returning an iterator over the
+    /// removed [`char`]s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is out of bounds or its ends do not lie
+    /// on char boundaries. The range is bounded by [`DStr::len`].
+    #[inline]
+    pub fn try_drain<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+    ) -> Result<Drain<'_, A>, IndexError> {
+        let range = self.try_resolve_range(range)?;
+
+        Ok(Drain::new(self, range))
+    }
+
+    /// Copies the given byte range to the end of this [`DString`], before the
+    /// nul terminator.
+    ///
+    /// Since the range is already known to be valid, this doesn't do any
+    /// checks on the copied data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its ends do not lie on char
+    /// boundaries. The range is bounded by [`DStr::len`].
+    #[inline]
+    #[track_caller]
+    pub fn extend_from_within<R: RangeBounds<usize>>(&mut self, range: R) {
+        let range = self.resolve_range(range);
+        self.buf_mut().reserve(range.len());
+
+        // The range lies on char boundaries and excludes the nul, so we copy
+        // valid UTF-8 without nuls and then re-terminate it.
+        self.buf_mut().pop();
+        self.buf_mut().extend_from_within(range);
+        self.buf_mut().push(0);
+    }
+
+    /// Resolves `range` against the string, excluding the nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its ends do not lie on char
+    /// boundaries.
+    #[track_caller]
+    fn resolve_range<R: RangeBounds<usize>>(&self, range: R) -> Range<usize> {
+        match self.try_resolve_range(range) {
+            Ok(range) => range,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Resolves `range` against the string, excluding the nul terminator.
+    ///
+    /// A range that starts after it ends is reported as out of bounds at its
+    /// start.
+    fn try_resolve_range<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> Result<Range<usize>, IndexError> {
+        let len = self.len();
+
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => match start.checked_add(1) {
+                Some(start) => start,
+                None => return Err(IndexError::OutOfBounds(start)),
+            },
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => match end.checked_add(1) {
+                Some(end) => end,
+                None => return Err(IndexError::OutOfBounds(end)),
+            },
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+
+        if start > end {
+            return Err(IndexError::OutOfBounds(start));
+        }
+
+        IndexError::check(self.as_str(), start)?;
+        IndexError::check(self.as_str(), end)?;
+
+        Ok(start..end)
+    }
+
+    /// Appends `string` to the end of this [`DString`], before the nul
+    /// terminator.
+    ///
+    /// Since a [`DStr`] is already known to be valid, this doesn't do any
+    /// checks.
+    #[inline]
+    pub fn push_dstr(&mut self, string: &DStr) {
+        // SAFETY: A `DStr` never contains nuls before its terminator.
+        unsafe { self.push_str_unchecked(string.as_str()) };
+    }
+
+    /// Appends `string` to the end of this [`DString`], before the nul
+    /// terminator.
+    ///
+    /// Since a [`CStr`] never contains interior nuls, only UTF-8 is checked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` is not valid UTF-8.
+    #[inline]
+    #[track_caller]
+    pub fn push_cstr(&mut self, string: &CStr) {
+        if let Err(err) = self.try_push_cstr(string) {
+            FromBytesError::InvalidUtf8(err)._panic()
+        }
+    }
+
+    /// Try to append `string` to the end of this [`DString`], before the nul
+    /// terminator.
+    ///
+    /// Since a [`CStr`] never contains interior nuls, only UTF-8 is checked.
+    /// Returns an error if `string` is not valid UTF-8, in which case nothing
+    /// is appended.
+    #[inline]
+    pub fn try_push_cstr(&mut self, string: &CStr) -> Result<(), Utf8Error> {
+        let string = string.to_str()?;
+
+        // SAFETY: A `CStr` never contains nuls before its terminator.
+        unsafe { self.push_str_unchecked(string) };
+
+        Ok(())
+    }
+
+    /// Appends `string` to the end of this [`DString`], before the nul
+    /// terminator, without doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `string` is valid UTF-8.
+    #[inline]
+    pub unsafe fn push_cstr_unchecked(&mut self, string: &CStr) {
+        // SAFETY: The caller ensures `string` is valid UTF-8, and a `CStr`
+        //         never contains nuls before its terminator.
+        unsafe { self.push_str_unchecked(from_utf8_unchecked(string.to_bytes())) };
+    }
+
     #[inline]
     #[must_use]
     pub fn as_dstr(&self) -> &DStr {
-        // unsafe { DStr::from_str_with_nul_unchecked(&self.inner) }
-        todo!()
+        unsafe { DStr::from_bytes_with_nul_unchecked(self.inner.as_ref()) }
     }
 
     #[inline]
     #[must_use]
     pub fn as_dstr_mut(&mut self) -> &mut DStr {
-        // unsafe { DStr::from_str_with_nul_unchecked_mut(&mut self.inner) }
-        todo!()
+        unsafe { DStr::from_bytes_with_nul_unchecked_mut(self.buf_mut()) }
+    }
+}
+
+/// A trait for converting a value to a [`DString`].
+///
+/// This is to [`DString`] what [`ToString`] is to [`String`], and is
+/// implemented for every type implementing [`fmt::Display`].
+///
+/// [`ToString`]: alloc::string::ToString
+pub trait ToDString {
+    /// Converts the given value to a [`DString`].
+    ///
+    /// Returns an error if the output contains a nul.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`fmt::Display`] implementation returns an error.
+    fn to_dstring(&self) -> Result<DString, NulError>;
+}
+
+impl<T: fmt::Display + ?Sized> ToDString for T {
+    #[inline]
+    fn to_dstring(&self) -> Result<DString, NulError> {
+        DString::try_from_fmt(format_args!("{self}"))
+    }
+}
+
+/// Formats into a [`DString`], remembering where a nul was written.
+struct FmtWriter {
+    string: DString,
+    error: Option<NulError>,
+}
+
+impl fmt::Write for FmtWriter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let len = self.string.len();
+
+        self.string.try_push_str(s).map_err(|err| {
+            self.error = Some(NulError::new(len + err.nul_position()));
+            fmt::Error
+        })
+    }
+}
+
+/// Moves the retained chars of a [`DString::retain`] into place once dropped,
+/// even if a panic occurs in between.
+struct Retain<'a, A: Allocator> {
+    string: &'a mut DString<A>,
+    idx: usize,
+    deleted: usize,
+}
+
+impl<A: Allocator> Drop for Retain<'_, A> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.deleted > 0 {
+            // This moves the unprocessed chars along with the nul terminator.
+            let inner = self.string.buf_mut();
+            inner.copy_within(self.idx.., self.idx - self.deleted);
+            inner.truncate(inner.len() - self.deleted);
+        }
     }
 }
 
-impl Deref for DString {
+impl Default for DString {
+    #[inline]
+    fn default() -> Self {
+        DString::new()
+    }
+}
+
+impl<A: Allocator + Clone> Clone for DString<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        #[cfg(feature = "allocator_api")]
+        {
+            // SAFETY: The buffer is copied from a valid `DString`.
+            unsafe { DString::from_buf_unchecked(self.inner.clone()) }
+        }
+
+        #[cfg(not(feature = "allocator_api"))]
+        {
+            DString {
+                inner: self.inner.clone(),
+                alloc: PhantomData,
+            }
+        }
+    }
+}
+
+impl<A: Allocator> PartialEq for DString<A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_dstr() == other.as_dstr()
+    }
+}
+
+impl<A: Allocator> Eq for DString<A> {}
+
+impl<A: Allocator> PartialOrd for DString<A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Allocator> Ord for DString<A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_dstr().cmp(other.as_dstr())
+    }
+}
+
+impl<A: Allocator> Hash for DString<A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_dstr().hash(state)
+    }
+}
+
+impl<A: Allocator> fmt::Debug for DString<A> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dstr(), f)
+    }
+}
+
+impl<A: Allocator> Deref for DString<A> {
     type Target = DStr;
 
     #[inline]
@@ -35,27 +1134,262 @@ impl Deref for DString {
     }
 }
 
-impl DerefMut for DString {
+impl<A: Allocator> DerefMut for DString<A> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_dstr_mut()
     }
 }
 
+/// Appends a string, see [`DString::push_str`].
+///
+/// # Panics
+///
+/// Panics if the string contains a nul.
+impl<A: Allocator> Add<&str> for DString<A> {
+    type Output = DString<A>;
+
+    #[inline]
+    #[track_caller]
+    fn add(mut self, rhs: &str) -> DString<A> {
+        self.push_str(rhs);
+        self
+    }
+}
+
+/// Appends a string, see [`DString::push_dstr`].
+impl<A: Allocator> Add<&DStr> for DString<A> {
+    type Output = DString<A>;
+
+    #[inline]
+    fn add(mut self, rhs: &DStr) -> DString<A> {
+        self.push_dstr(rhs);
+        self
+    }
+}
+
+/// Appends a string, see [`DString::push_str`].
+///
+/// # Panics
+///
+/// Panics if the string contains a nul.
+impl<A: Allocator> AddAssign<&str> for DString<A> {
+    #[inline]
+    #[track_caller]
+    fn add_assign(&mut self, rhs: &str) {
+        self.push_str(rhs);
+    }
+}
+
+/// Appends a string, see [`DString::push_dstr`].
+impl<A: Allocator> AddAssign<&DStr> for DString<A> {
+    #[inline]
+    fn add_assign(&mut self, rhs: &DStr) {
+        self.push_dstr(rhs);
+    }
+}
+
+/// Appends formatted output before the nul terminator.
+///
+/// Writing a nul returns an error, in which case nothing is written.
+impl<A: Allocator> fmt::Write for DString<A> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.try_push(c).map_err(|_| fmt::Error)
+    }
+}
+
+/// Appends every [`char`] before the nul terminator.
+///
+/// # Panics
+///
+/// Panics if any of the [`char`]s is a nul.
+impl<A: Allocator> Extend<char> for DString<A> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for ch in iter {
+            self.push(ch);
+        }
+    }
+}
+
+/// Appends every string before the nul terminator.
+///
+/// # Panics
+///
+/// Panics if any of the strings contains a nul.
+impl<'a, A: Allocator> Extend<&'a str> for DString<A> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for string in iter {
+            self.push_str(string);
+        }
+    }
+}
+
+/// Appends every string before the nul terminator.
+///
+/// Since a [`DStr`] is already known to be valid, this doesn't do any checks.
+impl<'a, A: Allocator> Extend<&'a DStr> for DString<A> {
+    fn extend<I: IntoIterator<Item = &'a DStr>>(&mut self, iter: I) {
+        for string in iter {
+            self.push_dstr(string);
+        }
+    }
+}
+
+/// Collects every [`char`] into a new [`DString`].
+///
+/// # Panics
+///
+/// Panics if any of the [`char`]s is a nul.
+impl FromIterator<char> for DString {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut string = DString::new();
+        string.extend(iter);
+        string
+    }
+}
+
+/// Concatenates every string into a new [`DString`].
+///
+/// # Panics
+///
+/// Panics if any of the strings contains a nul.
+impl<'a> FromIterator<&'a str> for DString {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut string = DString::new();
+        string.extend(iter);
+        string
+    }
+}
+
+/// Concatenates every string into a new [`DString`].
+impl<'a> FromIterator<&'a DStr> for DString {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = &'a DStr>>(iter: I) -> Self {
+        let mut string = DString::new();
+        string.extend(iter);
+        string
+    }
+}
+
 impl From<&DStr> for DString {
     #[inline]
     fn from(value: &DStr) -> Self {
-        DString {
-            inner: value.as_str_with_nul().to_owned(),
-        }
+        // SAFETY: The buffer is copied from a valid `DStr`.
+        unsafe { DString::from_buf_unchecked(value.as_bytes_with_nul().to_vec()) }
     }
 }
 
 impl From<&mut DStr> for DString {
     #[inline]
     fn from(value: &mut DStr) -> Self {
-        DString {
-            inner: value.as_str_with_nul().to_owned(),
+        DString::from(&*value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DArrayString, DSmallString};
+
+    /// Contains chars of one, two and three bytes.
+    const STRING: &str = "a\u{e9}\u{20ac}b";
+
+    fn dstring() -> DString {
+        let mut string = DString::new();
+        string.push_str(STRING);
+        string
+    }
+
+    fn check(idx: usize) -> Result<(), IndexError> {
+        if idx > STRING.len() {
+            Err(IndexError::OutOfBounds(idx))
+        } else if !STRING.is_char_boundary(idx) {
+            Err(IndexError::NotCharBoundary(idx))
+        } else {
+            Ok(())
         }
     }
+
+    #[test]
+    fn checked_indices_never_panic() {
+        for idx in 0..=STRING.len() + 2 {
+            let ok = check(idx);
+
+            let mut string = dstring();
+            assert_eq!(string.try_insert_str(idx, "x"), ok.map_err(EditError::from));
+            assert_eq!(string.len(), STRING.len() + usize::from(ok.is_ok()));
+
+            let mut string = dstring();
+            let nul = ok.map_or_else(EditError::from, |()| EditError::ContainsNul(0));
+            assert_eq!(string.try_insert(idx, '\0'), Err(nul));
+            assert_eq!(string.as_str(), STRING);
+
+            let mut string = dstring();
+            match (string.try_remove(idx), ok) {
+                (Ok(ch), Ok(())) => assert_eq!(STRING[idx..].chars().next(), Some(ch)),
+                (Err(err), Ok(())) => assert_eq!(err, IndexError::OutOfBounds(STRING.len())),
+                (result, Err(err)) => assert_eq!(result, Err(err)),
+            }
+
+            // Truncating to a longer length does nothing.
+            let truncated = match ok {
+                Err(IndexError::OutOfBounds(_)) => Ok(()),
+                ok => ok,
+            };
+            let len = STRING.len().min(idx);
+
+            let mut string = dstring();
+            assert_eq!(string.try_truncate(idx), truncated);
+            assert_eq!(string.as_bytes_with_nul().last(), Some(&0));
+
+            let mut array = DArrayString::<16>::try_from_str(STRING).unwrap();
+            assert_eq!(array.try_truncate(idx), truncated);
+            assert_eq!(array.len(), truncated.map_or(STRING.len(), |()| len));
+
+            for mut small in [DSmallString::new(), DSmallString::with_capacity(64)] {
+                small.push_str(STRING);
+                assert_eq!(small.try_truncate(idx), truncated);
+                assert_eq!(small.len(), truncated.map_or(STRING.len(), |()| len));
+                assert_eq!(small.as_bytes_with_nul().last(), Some(&0));
+            }
+
+            let mut string = dstring();
+            match string.try_split_off(idx) {
+                Ok(tail) => {
+                    assert_eq!(ok, Ok(()));
+                    assert_eq!(string.len() + tail.len(), STRING.len());
+                }
+                Err(err) => assert_eq!(Err(err), ok),
+            }
+
+            for end in 0..=STRING.len() + 2 {
+                let ok = match idx > end {
+                    true => Err(IndexError::OutOfBounds(idx)),
+                    false => ok.and(check(end)),
+                };
+
+                let mut string = dstring();
+                assert_eq!(string.try_drain(idx..end).map(|_| ()), ok);
+
+                let mut string = dstring();
+                let result = string.try_replace_range(idx..end, "x");
+                assert_eq!(result, ok.map_err(EditError::from));
+            }
+        }
+
+        let mut string = dstring();
+        let result = string.try_drain(..=usize::MAX).map(|_| ());
+        assert_eq!(result, Err(IndexError::OutOfBounds(usize::MAX)));
+    }
 }
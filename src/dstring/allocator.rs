@@ -0,0 +1,23 @@
+//! Stand-ins for the unstable allocator API, used when the `allocator_api`
+//! feature is disabled.
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A stand-in for [`core::alloc::Allocator`], which is only implemented by
+/// [`Global`].
+///
+/// Enable the `allocator_api` feature on a nightly compiler to use custom
+/// allocators with [`DString`].
+///
+/// [`DString`]: crate::DString
+pub trait Allocator: sealed::Sealed {}
+
+/// A stand-in for [`alloc::alloc::Global`], the global memory allocator.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Global;
+
+impl sealed::Sealed for Global {}
+
+impl Allocator for Global {}
@@ -0,0 +1,89 @@
+use core::{fmt, iter::FusedIterator, ops::Range};
+
+use crate::dstring::{Allocator, DString, Global};
+
+/// A draining iterator over a range of a [`DString`].
+///
+/// The drained range is removed once this is dropped, while the nul
+/// terminator always stays in place. If this is leaked, the [`DString`] is
+/// left untouched.
+///
+/// This is created by [`DString::drain`].
+pub struct Drain<'a, A: Allocator = Global> {
+    string: &'a mut DString<A>,
+    /// The range to remove once dropped.
+    range: Range<usize>,
+    /// The part of the range that was not yet yielded.
+    rest: Range<usize>,
+}
+
+impl<'a, A: Allocator> Drain<'a, A> {
+    /// The range must lie on char boundaries before the nul terminator.
+    #[inline]
+    pub(crate) fn new(string: &'a mut DString<A>, range: Range<usize>) -> Drain<'a, A> {
+        Drain {
+            string,
+            rest: range.clone(),
+            range,
+        }
+    }
+
+    /// Returns the remaining part of the drained range as a string slice.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: Both ends of `rest` always lie on char boundaries.
+        unsafe { self.string.as_str().get_unchecked(self.rest.clone()) }
+    }
+}
+
+impl<A: Allocator> fmt::Debug for Drain<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_str()).finish()
+    }
+}
+
+impl<A: Allocator> Iterator for Drain<'_, A> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next()?;
+        self.rest.start += ch.len_utf8();
+
+        Some(ch)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest.len();
+
+        (len.div_ceil(4), Some(len))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<char> {
+        self.next_back()
+    }
+}
+
+impl<A: Allocator> DoubleEndedIterator for Drain<'_, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next_back()?;
+        self.rest.end -= ch.len_utf8();
+
+        Some(ch)
+    }
+}
+
+impl<A: Allocator> FusedIterator for Drain<'_, A> {}
+
+impl<A: Allocator> Drop for Drain<'_, A> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: The range lies on char boundaries before the nul terminator,
+        //         so the remaining buffer is still valid.
+        unsafe { self.string.as_mut_vec() }.drain(self.range.clone());
+    }
+}
@@ -0,0 +1,102 @@
+use core::{
+    fmt,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+};
+
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::dstr::NulError;
+
+/// A guard giving access to the [`String`] inside a [`DString`], without the
+/// nul terminator.
+///
+/// Once the guard is dropped the nul terminator is put back in place. If a
+/// nul was added in the meantime, the string is truncated at the first nul,
+/// so that it is still valid. [`EditGuard::finish`] can be used instead to
+/// find out whether that happened.
+///
+/// While the guard is alive the [`DString`] holds an empty placeholder, so
+/// leaking the guard loses the edited string rather than leaving the
+/// [`DString`] without its terminator.
+///
+/// This is created by [`DString::edit`].
+///
+/// [`DString`]: crate::DString
+/// [`DString::edit`]: crate::DString::edit
+pub struct EditGuard<'a> {
+    target: &'a mut Vec<u8>,
+    string: String,
+}
+
+impl<'a> EditGuard<'a> {
+    #[inline]
+    pub(crate) fn new(target: &'a mut Vec<u8>) -> EditGuard<'a> {
+        let string = mem::replace(target, vec![0]);
+
+        // SAFETY: The buffer of a `DString` is always valid UTF-8.
+        let mut string = unsafe { String::from_utf8_unchecked(string) };
+
+        let nul = string.pop();
+        debug_assert_eq!(
+            nul,
+            Some('\0'),
+            "a nul-terminated string must end with a nul"
+        );
+
+        EditGuard { target, string }
+    }
+
+    /// Ends the edit, putting the nul terminator back in place.
+    ///
+    /// Returns an error if a nul was added, in which case the string is
+    /// truncated at the first nul.
+    #[inline]
+    pub fn finish(self) -> Result<(), NulError> {
+        ManuallyDrop::new(self).terminate()
+    }
+
+    #[inline]
+    fn terminate(&mut self) -> Result<(), NulError> {
+        let result = NulError::check(self.string.as_bytes());
+
+        if let Err(err) = result {
+            // A nul is a single byte, so it always lies on a char boundary.
+            self.string.truncate(err.nul_position());
+        }
+
+        self.string.push('\0');
+        *self.target = mem::take(&mut self.string).into_bytes();
+
+        result
+    }
+}
+
+impl Deref for EditGuard<'_> {
+    type Target = String;
+
+    #[inline]
+    fn deref(&self) -> &String {
+        &self.string
+    }
+}
+
+impl DerefMut for EditGuard<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut String {
+        &mut self.string
+    }
+}
+
+impl Drop for EditGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = self.terminate();
+    }
+}
+
+impl fmt::Debug for EditGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EditGuard").field(&self.string).finish()
+    }
+}
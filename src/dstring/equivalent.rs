@@ -0,0 +1,50 @@
+//! [`Equivalent`] impls, so maps keyed by [`DString`] can be looked up by a
+//! borrowed string.
+
+use hashbrown::Equivalent;
+
+use crate::DStr;
+
+use super::{Allocator, DString};
+
+/// A [`str`] is equivalent to a [`DString`] with the same content, as both
+/// hash the same.
+impl<A: Allocator> Equivalent<DString<A>> for str {
+    #[inline]
+    fn equivalent(&self, key: &DString<A>) -> bool {
+        self == key.as_str()
+    }
+}
+
+impl<A: Allocator> Equivalent<DString<A>> for DStr {
+    #[inline]
+    fn equivalent(&self, key: &DString<A>) -> bool {
+        self == key.as_dstr()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::hash::{BuildHasherDefault, DefaultHasher};
+
+    use hashbrown::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn lookup_without_an_owned_key() {
+        let mut map = HashMap::<DString, usize, BuildHasherDefault<DefaultHasher>>::default();
+
+        for (idx, key) in ["", "a", "key", "\u{e9}t\u{e9}"].into_iter().enumerate() {
+            let mut string = DString::new();
+            string.push_str(key);
+            map.insert(string, idx);
+        }
+
+        assert_eq!(map.get("key"), Some(&2));
+        assert_eq!(map.get("\u{e9}t\u{e9}"), Some(&3));
+        assert_eq!(map.get(DStr::from_str_with_nul("a\0")), Some(&1));
+        assert_eq!(map.get(DStr::EMPTY), Some(&0));
+        assert_eq!(map.get("missing"), None);
+    }
+}
@@ -0,0 +1,80 @@
+use core::borrow::Borrow;
+
+use alloc::string::String;
+
+use crate::{DStr, DString};
+
+/// Concatenates every string into a new [`DString`].
+///
+/// This behaves the same as [`slice::concat`] on a slice of [`str`]s, and the
+/// result is built with a single allocation.
+///
+/// # Panics
+///
+/// Panics if the capacity would overflow.
+#[must_use]
+pub fn concat<S: Borrow<DStr>>(slice: &[S]) -> DString {
+    join(slice, DStr::EMPTY)
+}
+
+/// Joins every string into a new [`DString`], placing `sep` between each of
+/// them.
+///
+/// This behaves the same as [`slice::join`] on a slice of [`str`]s, and the
+/// result is built with a single allocation.
+///
+/// # Panics
+///
+/// Panics if the capacity would overflow.
+#[must_use]
+pub fn join<S: Borrow<DStr>>(slice: &[S], sep: &DStr) -> DString {
+    let seps = slice.len().saturating_sub(1);
+    let capacity = sep
+        .len()
+        .checked_mul(seps)
+        .and_then(|len| added_len(slice, len))
+        .expect("capacity overflow");
+
+    let mut string = DString::with_capacity(capacity);
+
+    for (idx, part) in slice.iter().enumerate() {
+        if idx != 0 {
+            string.push_dstr(sep);
+        }
+
+        string.push_dstr(part.borrow());
+    }
+
+    string
+}
+
+/// Joins every string into a new [`String`], keeping the nul terminator of
+/// each of them.
+///
+/// This creates a buffer of consecutive nul-terminated strings, as used by
+/// some C APIs, and is built with a single allocation. An empty slice results
+/// in an empty buffer.
+///
+/// # Panics
+///
+/// Panics if the capacity would overflow.
+#[must_use]
+pub fn join_with_nul<S: Borrow<DStr>>(slice: &[S]) -> String {
+    let capacity = added_len(slice, slice.len()).expect("capacity overflow");
+
+    let mut string = String::with_capacity(capacity);
+
+    for part in slice {
+        string.push_str(part.borrow().as_str_with_nul());
+    }
+
+    string
+}
+
+/// Adds the lengths of every string to `len`, excluding their nul terminators.
+#[inline]
+fn added_len<S: Borrow<DStr>>(slice: &[S], len: usize) -> Option<usize> {
+    slice
+        .iter()
+        .try_fold(len, |len, part| len.checked_add(part.borrow().len()))
+}
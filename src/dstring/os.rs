@@ -0,0 +1,187 @@
+use core::fmt;
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    string::String,
+};
+
+use crate::{
+    dstr::{msg, DStrError, ErrorKind, FromBytesError, NulError},
+    mem,
+};
+
+use super::DString;
+
+/// An error indicating that an [`OsString`] could not be converted into a
+/// [`DString`].
+///
+/// The original string can be recovered with
+/// [`FromOsStringError::into_os_string`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromOsStringError {
+    string: OsString,
+    /// The position of the first nul, or `None` if the string is not valid
+    /// UTF-8.
+    nul_pos: Option<usize>,
+}
+
+impl FromOsStringError {
+    /// Returns the position of the first nul, or `None` if the string is not
+    /// valid UTF-8.
+    #[inline]
+    #[must_use]
+    pub const fn nul_position(&self) -> Option<usize> {
+        self.nul_pos
+    }
+
+    /// Returns the string that could not be converted.
+    #[inline]
+    #[must_use]
+    pub fn as_os_string(&self) -> &OsString {
+        &self.string
+    }
+
+    /// Consumes this error, returning the string that could not be converted.
+    #[inline]
+    #[must_use]
+    pub fn into_os_string(self) -> OsString {
+        self.string
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn message(&self) -> &'static str {
+        match self.nul_pos {
+            Some(_) => msg!(ContainsNul),
+            None => msg!(InvalidUtf8),
+        }
+    }
+}
+
+impl fmt::Display for FromOsStringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        if let Some(pos) = self.nul_pos {
+            core::write!(f, " at pos {pos}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for FromOsStringError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<FromOsStringError> for DStrError {
+    /// The [`OsString`] is dropped, so use
+    /// [`FromOsStringError::into_os_string`] first to keep it.
+    #[inline]
+    fn from(value: FromOsStringError) -> Self {
+        match value.nul_pos {
+            Some(nul_pos) => DStrError::new(ErrorKind::ContainsNul, Some(nul_pos)),
+            None => DStrError::new(ErrorKind::InvalidUtf8, None),
+        }
+    }
+}
+
+impl DString {
+    /// Converts this [`DString`] into a [`PathBuf`] without copying, dropping
+    /// the nul terminator.
+    #[inline]
+    #[must_use]
+    pub fn into_path_buf(self) -> PathBuf {
+        OsString::from(self).into()
+    }
+}
+
+impl AsRef<Path> for DString {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.to_path()
+    }
+}
+
+impl From<DString> for OsString {
+    /// Converts the string without copying, dropping the nul terminator.
+    #[inline]
+    fn from(value: DString) -> Self {
+        let mut bytes = value.into_bytes_with_nul();
+        bytes.pop();
+
+        // SAFETY: The buffer of a `DString` is valid UTF-8.
+        unsafe { String::from_utf8_unchecked(bytes) }.into()
+    }
+}
+
+impl TryFrom<OsString> for DString {
+    type Error = FromOsStringError;
+
+    /// Converts the string, reusing its allocation.
+    ///
+    /// Returns an error if the string is not valid UTF-8 or contains a nul.
+    #[inline]
+    fn try_from(value: OsString) -> Result<Self, Self::Error> {
+        let mut string = value.into_string().map_err(|string| FromOsStringError {
+            string,
+            nul_pos: None,
+        })?;
+
+        if let Err(err) = NulError::check(string.as_bytes()) {
+            return Err(FromOsStringError {
+                string: string.into(),
+                nul_pos: Some(err.nul_position()),
+            });
+        }
+
+        string.push('\0');
+
+        // SAFETY: We just checked that the string contains no nuls.
+        Ok(unsafe { DString::from_string_with_nul_unchecked(string) })
+    }
+}
+
+impl From<DString> for PathBuf {
+    #[inline]
+    fn from(value: DString) -> Self {
+        value.into_path_buf()
+    }
+}
+
+impl TryFrom<&Path> for DString {
+    type Error = FromBytesError;
+
+    /// Copies the path, adding a nul terminator.
+    ///
+    /// Returns an error if the path is not valid UTF-8 or contains a nul.
+    #[inline]
+    fn try_from(value: &Path) -> Result<Self, Self::Error> {
+        let string = mem::validate_utf8(value.as_os_str().as_encoded_bytes())
+            .map_err(FromBytesError::InvalidUtf8)?;
+
+        let mut dstring = DString::with_capacity(string.len());
+        dstring
+            .try_push_str(string)
+            .map_err(|err| FromBytesError::InteriorNul(err.nul_position()))?;
+
+        Ok(dstring)
+    }
+}
+
+impl TryFrom<PathBuf> for DString {
+    type Error = FromOsStringError;
+
+    /// Converts the path, reusing its allocation.
+    ///
+    /// Returns an error if the path is not valid UTF-8 or contains a nul.
+    #[inline]
+    fn try_from(value: PathBuf) -> Result<Self, Self::Error> {
+        DString::try_from(value.into_os_string())
+    }
+}
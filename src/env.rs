@@ -0,0 +1,152 @@
+//! Module for reading and changing the environment of the current process
+//! with nul-terminated UTF-8 strings.
+
+#[cfg(unix)]
+use core::ffi::{c_char, c_int};
+#[cfg(unix)]
+use std::io;
+use std::{
+    env::{self, VarError},
+    ffi::OsStr,
+    string::String,
+};
+
+use crate::{argv::EnvError, dstr::NulError, DStr, DString};
+
+/// Fetches the environment variable `key` of the current process.
+///
+/// This behaves the same as [`env::var`], but returns a [`DString`] that
+/// reuses the allocation of the value.
+///
+/// # Errors
+///
+/// Returns an error if the variable is not set, or its value is not valid
+/// UTF-8.
+#[inline]
+pub fn var_dstring<K: AsRef<OsStr>>(key: K) -> Result<DString, VarError> {
+    env::var(key).map(into_dstring)
+}
+
+/// Returns an iterator over the environment variables of the current process,
+/// as taken at the time of this call.
+///
+/// This behaves the same as [`env::vars`].
+///
+/// # Panics
+///
+/// The iterator panics if a key or value is not valid UTF-8.
+#[inline]
+pub fn vars_dstring() -> VarsDString {
+    VarsDString { inner: env::vars() }
+}
+
+/// An iterator over the environment variables of the current process.
+///
+/// This is created by [`vars_dstring`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct VarsDString {
+    inner: env::Vars,
+}
+
+impl Iterator for VarsDString {
+    type Item = (DString, DString);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.inner.next()?;
+
+        Some((into_dstring(key), into_dstring(value)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Sets the environment variable `key` of the current process to `value`.
+///
+/// This behaves the same as [`env::set_var`]. On unix, both strings are passed
+/// straight to `setenv` in C, since they are already nul-terminated, so this
+/// neither checks them for nuls again nor allocates. Elsewhere, this is a
+/// convenience wrapper around [`env::set_var`].
+///
+/// # Safety
+///
+/// The same as for [`env::set_var`] in the 2024 edition: no other thread may
+/// read or write the environment at the same time, including through
+/// functions such as `getenv` in C.
+///
+/// # Panics
+///
+/// Panics if `key` is empty or contains a `=`, or if the environment could
+/// not be changed.
+#[inline]
+#[track_caller]
+pub unsafe fn set_var(key: &DStr, value: &DStr) {
+    // SAFETY: The caller upholds the requirements of `try_set_var`.
+    if let Err(err) = unsafe { try_set_var(key, value) } {
+        err._panic()
+    }
+}
+
+/// Try to set the environment variable `key` of the current process to
+/// `value`.
+///
+/// This is the same as [`set_var`], but reports an invalid key the same way
+/// as [`EnvpBuilder::try_push`](crate::EnvpBuilder::try_push).
+///
+/// # Errors
+///
+/// Returns an error if `key` is empty or contains a `=`, in which case the
+/// environment is left unchanged.
+///
+/// # Safety
+///
+/// The same as for [`set_var`].
+///
+/// # Panics
+///
+/// Panics if the environment could not be changed.
+#[inline]
+#[track_caller]
+pub unsafe fn try_set_var(key: &DStr, value: &DStr) -> Result<(), EnvError> {
+    EnvError::check(key.as_str(), value.as_str())?;
+
+    #[cfg(unix)]
+    {
+        extern "C" {
+            fn setenv(name: *const c_char, value: *const c_char, overwrite: c_int) -> c_int;
+        }
+
+        // SAFETY: Both strings are nul-terminated, and the caller ensures that
+        //         nothing else accesses the environment at the same time.
+        if unsafe { setenv(key.as_c_ptr(), value.as_c_ptr(), 1) } != 0 {
+            panic!(
+                "failed to set environment variable `{key:?}` to `{value:?}`: {}",
+                io::Error::last_os_error()
+            );
+        }
+    }
+
+    #[cfg(not(unix))]
+    env::set_var(key.as_str(), value.as_str());
+
+    Ok(())
+}
+
+/// Converts an environment string into a [`DString`], which never fails.
+#[inline]
+#[track_caller]
+fn into_dstring(mut string: String) -> DString {
+    // The environment is made of nul-terminated strings on every platform.
+    if let Err(err) = NulError::check(string.as_bytes()) {
+        err._panic()
+    }
+
+    string.push('\0');
+
+    // SAFETY: We just checked that the string contains no nuls.
+    unsafe { DString::from_string_with_nul_unchecked(string) }
+}
@@ -0,0 +1,214 @@
+//! Module for owned nul-terminated UTF-8 strings allocated by foreign code.
+
+use core::{
+    cmp::Ordering,
+    ffi::{c_char, c_void},
+    fmt,
+    hash::{Hash, Hasher},
+    mem::ManuallyDrop,
+    ops::Deref,
+    ptr::{self, NonNull},
+    slice::from_raw_parts,
+    str::Utf8Error,
+};
+
+use crate::{mem, DStr};
+
+/// A way of freeing strings allocated by foreign code.
+///
+/// This is implemented for every `FnMut(*mut c_char)`, so a library-specific
+/// destructor can be passed as is.
+pub trait Dealloc {
+    /// Frees the string at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` was allocated in a way that this
+    /// deallocator can free, and is not used afterwards.
+    unsafe fn dealloc(&mut self, ptr: *mut c_char);
+}
+
+impl<F: FnMut(*mut c_char)> Dealloc for F {
+    #[inline]
+    unsafe fn dealloc(&mut self, ptr: *mut c_char) {
+        self(ptr)
+    }
+}
+
+/// Frees strings with the C `free` function, such as those returned by
+/// `strdup`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Free;
+
+impl Dealloc for Free {
+    #[inline]
+    unsafe fn dealloc(&mut self, ptr: *mut c_char) {
+        extern "C" {
+            fn free(ptr: *mut c_void);
+        }
+
+        // SAFETY: The caller ensures `ptr` was allocated by `malloc`.
+        unsafe { free(ptr.cast()) }
+    }
+}
+
+/// An owned nul-terminated UTF-8 string allocated by foreign code, which is
+/// freed with `D` once dropped.
+///
+/// The string is checked to be valid UTF-8 when created, and the length is
+/// stored, so it does not need to be computed again.
+pub struct ForeignDString<D: Dealloc> {
+    ptr: NonNull<c_char>,
+    len: usize,
+    dealloc: D,
+}
+
+// SAFETY: The string is owned, so it can be sent when the deallocator can.
+unsafe impl<D: Dealloc + Send> Send for ForeignDString<D> {}
+
+// SAFETY: The string is immutable, and the deallocator is only used on drop.
+unsafe impl<D: Dealloc + Sync> Sync for ForeignDString<D> {}
+
+impl<D: Dealloc> ForeignDString<D> {
+    /// Takes ownership of a foreign string, checking that it is valid UTF-8.
+    ///
+    /// Returns an error if the string is not valid UTF-8, in which case it is
+    /// freed with `dealloc` right away.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the following:
+    ///
+    /// - `ptr` is not null, and points to a nul-terminated string.
+    ///
+    /// - The nul is within [`isize::MAX`] bytes from `ptr`.
+    ///
+    /// - `ptr` can be freed with `dealloc`, and is not used elsewhere
+    ///   afterwards.
+    #[inline]
+    pub unsafe fn from_raw(
+        ptr: *mut c_char,
+        mut dealloc: D,
+    ) -> Result<ForeignDString<D>, Utf8Error> {
+        // SAFETY: The caller ensures `ptr` points to a nul-terminated string.
+        let len = unsafe { mem::find_nul(ptr) };
+
+        // SAFETY: The string is `len` bytes long, excluding the nul.
+        let bytes = unsafe { from_raw_parts(ptr.cast_const().cast::<u8>(), len) };
+
+        if let Err(err) = mem::validate_utf8(bytes) {
+            // SAFETY: The caller ensures `ptr` can be freed with `dealloc`.
+            unsafe { dealloc.dealloc(ptr) };
+
+            return Err(err);
+        }
+
+        Ok(ForeignDString {
+            // SAFETY: The caller ensures `ptr` is not null.
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            len,
+            dealloc,
+        })
+    }
+
+    /// Takes ownership of a foreign string without doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the same as for [`ForeignDString::from_raw`],
+    /// and additionally that the string is valid UTF-8.
+    #[inline]
+    #[must_use]
+    pub unsafe fn from_raw_unchecked(ptr: *mut c_char, dealloc: D) -> ForeignDString<D> {
+        debug_assert!(!ptr.is_null(), "a `ForeignDString` must not be null");
+
+        ForeignDString {
+            // SAFETY: The caller ensures `ptr` is not null.
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            // SAFETY: The caller ensures `ptr` points to a nul-terminated string.
+            len: unsafe { mem::find_nul(ptr) },
+            dealloc,
+        }
+    }
+
+    /// Consumes this [`ForeignDString`] without freeing it, returning the
+    /// pointer and the deallocator.
+    #[inline]
+    #[must_use = "losing the pointer will leak memory"]
+    pub fn into_raw(self) -> (*mut c_char, D) {
+        let this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used or dropped again.
+        let dealloc = unsafe { ptr::read(&this.dealloc) };
+
+        (this.ptr.as_ptr(), dealloc)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn as_dstr(&self) -> &DStr {
+        // SAFETY: The string is valid for the lifetime of `self`, and the nul
+        //         is at `len`.
+        let bytes =
+            unsafe { from_raw_parts(self.ptr.as_ptr().cast_const().cast::<u8>(), self.len + 1) };
+
+        // SAFETY: The string was checked to be valid UTF-8 when created, and the
+        //         nul at `len` is the first one.
+        unsafe { DStr::from_bytes_with_nul_unchecked(bytes) }
+    }
+}
+
+impl<D: Dealloc> Drop for ForeignDString<D> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: The string is owned, and was allocated compatibly with
+        //         `dealloc`.
+        unsafe { self.dealloc.dealloc(self.ptr.as_ptr()) }
+    }
+}
+
+impl<D: Dealloc> PartialEq for ForeignDString<D> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_dstr() == other.as_dstr()
+    }
+}
+
+impl<D: Dealloc> Eq for ForeignDString<D> {}
+
+impl<D: Dealloc> PartialOrd for ForeignDString<D> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D: Dealloc> Ord for ForeignDString<D> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_dstr().cmp(other.as_dstr())
+    }
+}
+
+impl<D: Dealloc> Hash for ForeignDString<D> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_dstr().hash(state)
+    }
+}
+
+impl<D: Dealloc> fmt::Debug for ForeignDString<D> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dstr(), f)
+    }
+}
+
+impl<D: Dealloc> Deref for ForeignDString<D> {
+    type Target = DStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_dstr()
+    }
+}
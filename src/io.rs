@@ -0,0 +1,17 @@
+//! Module for reading and writing nul-delimited records.
+
+mod records;
+pub use records::*;
+
+mod validator;
+pub use validator::*;
+
+#[cfg(feature = "std")]
+mod buf_read;
+#[cfg(feature = "std")]
+pub use buf_read::*;
+
+#[cfg(feature = "std")]
+mod write;
+#[cfg(feature = "std")]
+pub use write::*;
@@ -0,0 +1,204 @@
+use std::{
+    io::{self, BufRead},
+    vec::Vec,
+};
+
+use crate::{mem, DString};
+
+use super::{Status, ValidateError, Validator};
+
+/// An extension trait for reading nul-terminated UTF-8 strings from a
+/// [`BufRead`].
+///
+/// This is implemented for every [`BufRead`].
+pub trait BufReadExt: BufRead {
+    /// Reads up to and including the next nul, appending the string before it
+    /// to `buf`.
+    ///
+    /// Returns the amount of bytes read, including the nul, which is zero at
+    /// the end of the stream. The last record of the stream does not have to
+    /// be terminated.
+    ///
+    /// Like [`BufRead::read_line`], this appends to `buf`, so it can be
+    /// [cleared](DString::clear) and reused for every record. The record is
+    /// validated with a [`Validator`] as it is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the record
+    /// is not valid UTF-8, or any error returned by [`BufRead::fill_buf`]. In
+    /// both cases `buf` is left unchanged, but the bytes read so far are
+    /// consumed. An invalid record is consumed up to and including its nul.
+    fn read_until_nul(&mut self, buf: &mut DString) -> io::Result<usize> {
+        // SAFETY: The buffer is put back into a valid state once `guard` is
+        //         dropped, see `Append`.
+        let mut guard = Append {
+            start: buf.len(),
+            buf: unsafe { buf.as_mut_vec() },
+            valid: false,
+        };
+
+        // Read over the current nul terminator.
+        guard.buf.pop();
+
+        let mut validator = Validator::new();
+        let mut read = 0;
+
+        loop {
+            let available = match self.fill_buf() {
+                Ok(available) => available,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+
+            if available.is_empty() {
+                validator.finish().map_err(invalid_data)?;
+                break;
+            }
+
+            match validator.feed(available) {
+                Ok(Status::Nul(nul)) => {
+                    guard.buf.extend_from_slice(&available[..nul]);
+                    self.consume(nul + 1);
+                    read += nul + 1;
+                    break;
+                }
+                Ok(_) => {
+                    let len = available.len();
+
+                    guard.buf.extend_from_slice(available);
+                    self.consume(len);
+                    read += len;
+                }
+                Err(err) => {
+                    skip_until_nul(self)?;
+                    return Err(invalid_data(err));
+                }
+            }
+        }
+
+        guard.buf.push(0);
+        guard.valid = true;
+
+        Ok(read)
+    }
+
+    /// Reads up to and including the next nul, returning the string before it,
+    /// or `None` at the end of the stream.
+    ///
+    /// The last record of the stream does not have to be terminated. To reuse
+    /// a buffer for every record, see [`BufReadExt::read_until_nul`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the record
+    /// is not valid UTF-8, or any error returned by [`BufRead::fill_buf`].
+    fn read_dstring(&mut self) -> io::Result<Option<DString>> {
+        let mut buf = DString::new();
+
+        match self.read_until_nul(&mut buf)? {
+            0 => Ok(None),
+            _ => Ok(Some(buf)),
+        }
+    }
+
+    /// Returns an iterator over the nul-terminated records of this reader,
+    /// such as the output of `find -print0`.
+    ///
+    /// Each record is read with [`BufReadExt::read_dstring`].
+    #[inline]
+    fn records(self) -> ReadRecords<Self>
+    where
+        Self: Sized,
+    {
+        ReadRecords { reader: self }
+    }
+}
+
+impl<R: BufRead + ?Sized> BufReadExt for R {}
+
+/// An iterator over the nul-terminated records of a [`BufRead`].
+///
+/// An invalid record does not stop the iteration, as its bytes are consumed
+/// regardless.
+///
+/// This is created by [`BufReadExt::records`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ReadRecords<R> {
+    reader: R,
+}
+
+impl<R> ReadRecords<R> {
+    /// Returns a reference to the underlying reader.
+    #[inline]
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consumes this [`ReadRecords`], returning the underlying reader.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> Iterator for ReadRecords<R> {
+    type Item = io::Result<DString>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader.read_dstring().transpose()
+    }
+}
+
+/// Consumes everything up to and including the next nul.
+fn skip_until_nul<R: BufRead + ?Sized>(reader: &mut R) -> io::Result<()> {
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        if available.is_empty() {
+            return Ok(());
+        }
+
+        match mem::find_byte(0, available) {
+            Some(nul) => {
+                reader.consume(nul + 1);
+                return Ok(());
+            }
+            None => {
+                let len = available.len();
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+#[inline]
+fn invalid_data(err: ValidateError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Removes the bytes appended to the buffer of a [`DString`] once dropped,
+/// unless they were validated, even if a panic occurs in between.
+struct Append<'a> {
+    buf: &'a mut Vec<u8>,
+    start: usize,
+    valid: bool,
+}
+
+impl Drop for Append<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.valid {
+            self.buf.truncate(self.start);
+            self.buf.push(0);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use core::{borrow::Borrow, iter::FusedIterator};
+
+use crate::{
+    dstr::{FromBytesError, NulError, PushError},
+    mem, DStr, DStrList,
+};
+
+/// Returns an iterator over the nul-terminated records in `bytes`, such as the
+/// output of `find -print0`.
+///
+/// Each record is validated as it is yielded, so an invalid record does not
+/// stop the iteration.
+#[inline]
+pub fn records(bytes: &[u8]) -> Records<'_> {
+    Records { rest: bytes }
+}
+
+/// An iterator over the nul-terminated records in a byte slice.
+///
+/// If the bytes do not end with a nul, the last item is an error of
+/// [`FromBytesError::NotNulTerminated`], and the unterminated record can be
+/// obtained through [`Records::remainder`] beforehand.
+///
+/// This is created by [`records`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Records<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Records<'a> {
+    /// Returns the bytes that were not yielded yet.
+    #[inline]
+    #[must_use]
+    pub const fn remainder(&self) -> &'a [u8] {
+        self.rest
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Result<&'a DStr, FromBytesError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let rest = self.rest;
+
+        Some(match mem::validate_until_nul(rest) {
+            Ok(Some(nul)) => {
+                let (record, tail) = rest.split_at(nul + 1);
+                self.rest = tail;
+
+                // SAFETY: The record is valid UTF-8 and ends at the first nul.
+                Ok(unsafe { DStr::from_bytes_with_nul_unchecked(record) })
+            }
+            Ok(None) => {
+                self.rest = &[];
+                Err(FromBytesError::NotNulTerminated)
+            }
+            Err(_) => {
+                let end = mem::find_byte(0, rest).map_or(rest.len(), |nul| nul + 1);
+                let (record, tail) = rest.split_at(end);
+                self.rest = tail;
+
+                Err(FromBytesError::InvalidUtf8(mem::utf8_error(record)))
+            }
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every record is at least one byte.
+        (usize::from(!self.rest.is_empty()), Some(self.rest.len()))
+    }
+}
+
+impl FusedIterator for Records<'_> {}
+
+/// A writer of nul-terminated records into a borrowed buffer, the producing
+/// counterpart to [`records`].
+///
+/// Every record is written along with its nul terminator, so the written
+/// bytes always form a [`DStrList`]. Records that do not fit fail without
+/// writing anything.
+#[derive(Debug)]
+pub struct RecordWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> RecordWriter<'a> {
+    /// Creates a new [`RecordWriter`] writing into `buf`.
+    #[inline]
+    #[must_use]
+    pub fn new(buf: &'a mut [u8]) -> RecordWriter<'a> {
+        RecordWriter { buf, len: 0 }
+    }
+
+    /// Returns the amount of bytes written, including every nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether nothing was written yet.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the size of the buffer in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns how many more bytes fit in the buffer.
+    #[inline]
+    #[must_use]
+    pub const fn remaining_capacity(&self) -> usize {
+        self.buf.len() - self.len
+    }
+
+    /// Writes `string` along with its nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` does not fit.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, string: &DStr) {
+        if let Err(err) = self.try_push(string) {
+            err._panic()
+        }
+    }
+
+    /// Try to write `string` along with its nul terminator.
+    ///
+    /// Returns [`PushError::CapacityExceeded`] if `string` does not fit, in
+    /// which case nothing is written.
+    #[inline]
+    pub fn try_push(&mut self, string: &DStr) -> Result<(), PushError> {
+        let bytes = string.as_bytes_with_nul();
+        let end = self.len + bytes.len();
+
+        match self.buf.get_mut(self.len..end) {
+            Some(dst) => dst.copy_from_slice(bytes),
+            None => return Err(PushError::CapacityExceeded),
+        }
+
+        self.len = end;
+
+        Ok(())
+    }
+
+    /// Try to write `string` followed by a nul terminator.
+    ///
+    /// Returns an error if `string` contains a nul or does not fit, in which
+    /// case nothing is written.
+    #[inline]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), PushError> {
+        let end = self.len + string.len() + 1;
+
+        if end > self.buf.len() {
+            return Err(PushError::CapacityExceeded);
+        }
+
+        NulError::check(string.as_bytes())?;
+
+        self.buf[self.len..end - 1].copy_from_slice(string.as_bytes());
+        self.buf[end - 1] = 0;
+        self.len = end;
+
+        Ok(())
+    }
+
+    /// Try to write every string of `iter`, each along with its nul
+    /// terminator.
+    ///
+    /// Stops at the first string that does not fit, keeping the ones written
+    /// before it.
+    #[inline]
+    pub fn try_push_all<I>(&mut self, iter: I) -> Result<(), PushError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<DStr>,
+    {
+        iter.into_iter()
+            .try_for_each(|string| self.try_push(string.borrow()))
+    }
+
+    /// Removes every record written so far.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns the bytes written, including every nul terminator.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the records written as a [`DStrList`].
+    #[inline]
+    #[must_use]
+    pub fn as_list(&self) -> &DStrList {
+        // SAFETY: Only nul-terminated UTF-8 records are ever written.
+        unsafe { DStrList::from_str_unchecked(core::str::from_utf8_unchecked(self.as_bytes())) }
+    }
+
+    /// Consumes this [`RecordWriter`], returning the records written as a
+    /// [`DStrList`].
+    #[inline]
+    #[must_use]
+    pub fn finish(self) -> &'a DStrList {
+        let bytes = &self.buf[..self.len];
+
+        // SAFETY: Only nul-terminated UTF-8 records are ever written.
+        unsafe { DStrList::from_str_unchecked(core::str::from_utf8_unchecked(bytes)) }
+    }
+}
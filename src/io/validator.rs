@@ -0,0 +1,359 @@
+use core::{fmt, str::from_utf8};
+
+use crate::{
+    dstr::{msg, DStrError, ErrorKind},
+    mem,
+};
+
+/// A push-based validator for nul-terminated UTF-8 strings that arrive in
+/// chunks, such as from a socket.
+///
+/// Each chunk is validated as it is [fed](Validator::feed), including any
+/// multi-byte sequences that are split across chunks, so the string never
+/// has to be buffered as a whole to be validated.
+///
+/// The first nul ends the string. The validator then starts over with the
+/// next chunk, so a stream of nul-terminated records can be validated by
+/// feeding every chunk after the nul once again.
+#[derive(Debug, Clone, Default)]
+pub struct Validator {
+    /// The length of the string validated so far, excluding `partial`.
+    len: usize,
+    /// The start of a multi-byte sequence that was split across chunks.
+    partial: [u8; 4],
+    partial_len: u8,
+    /// Whether the last chunk ended the string with a nul.
+    terminated: bool,
+    error: Option<ValidateError>,
+}
+
+/// The status of a [`Validator`] after it was fed a chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The chunk was valid, and ended on a char boundary.
+    Complete,
+    /// The chunk was valid, but ended in the middle of a multi-byte sequence.
+    Incomplete,
+    /// The chunk contained a nul at the given position, which ends the
+    /// string. Everything before it was valid, and nothing after it was
+    /// validated.
+    Nul(usize),
+}
+
+impl Validator {
+    /// Creates a new [`Validator`].
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Validator {
+        Validator {
+            len: 0,
+            partial: [0; 4],
+            partial_len: 0,
+            terminated: false,
+            error: None,
+        }
+    }
+
+    /// Returns the length in bytes of the string validated so far, excluding
+    /// the start of a multi-byte sequence that is not yet complete.
+    ///
+    /// Once the string is ended by a nul, this is its length until the next
+    /// chunk is fed.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether nothing of the string was validated so far.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0 && self.partial_len == 0
+    }
+
+    /// Resets this [`Validator`] to start over with a new string, clearing any
+    /// error.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Validator::new();
+    }
+
+    /// Validates the next chunk of the string.
+    ///
+    /// If the previous chunk ended the string with a nul, this starts a new
+    /// string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not valid UTF-8, positioned relative
+    /// to the start of the string. Once an error is returned, the same error
+    /// is returned for every chunk until this [`Validator`] is
+    /// [reset](Validator::reset).
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Status, ValidateError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        if self.terminated {
+            self.len = 0;
+            self.terminated = false;
+        }
+
+        let result = self.feed_inner(chunk);
+
+        if let Err(err) = result {
+            self.error = Some(err);
+        }
+
+        result
+    }
+
+    fn feed_inner(&mut self, chunk: &[u8]) -> Result<Status, ValidateError> {
+        let mut start = 0;
+
+        if self.partial_len != 0 {
+            match self.complete_partial(chunk)? {
+                Some(read) => start = read,
+                None => return Ok(Status::Incomplete),
+            }
+        }
+
+        let rest = &chunk[start..];
+
+        match mem::validate_until_nul(rest) {
+            Ok(Some(nul)) => {
+                self.len += nul;
+                self.terminated = true;
+
+                Ok(Status::Nul(start + nul))
+            }
+            Ok(None) => {
+                self.len += rest.len();
+
+                Ok(Status::Complete)
+            }
+            Err(valid) => {
+                self.len += valid;
+
+                let tail = &rest[valid..];
+                let window = &tail[..tail.len().min(4)];
+
+                match mem::utf8_error(window).error_len() {
+                    // The chunk ends in the middle of a sequence.
+                    None if window.len() == tail.len() => {
+                        self.partial[..tail.len()].copy_from_slice(tail);
+                        self.partial_len = tail.len() as u8;
+
+                        Ok(Status::Incomplete)
+                    }
+                    error_len => Err(ValidateError {
+                        valid_up_to: self.len,
+                        error_len: error_len.map(|len| len as u8),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Completes the multi-byte sequence split across chunks with the start
+    /// of `chunk`, returning how many bytes of `chunk` it took, or `None` if
+    /// `chunk` ends before the sequence does.
+    fn complete_partial(&mut self, chunk: &[u8]) -> Result<Option<usize>, ValidateError> {
+        let have = self.partial_len as usize;
+        let width = match self.partial[0] {
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            _ => 4,
+        };
+
+        let read = (width - have).min(chunk.len());
+        let mut buf = self.partial;
+        buf[have..have + read].copy_from_slice(&chunk[..read]);
+
+        let sequence = &buf[..have + read];
+
+        match from_utf8(sequence) {
+            Ok(_) => {
+                self.len += width;
+                self.partial_len = 0;
+
+                Ok(Some(read))
+            }
+            Err(err) if err.error_len().is_none() => {
+                self.partial = buf;
+                self.partial_len = sequence.len() as u8;
+
+                Ok(None)
+            }
+            Err(err) => Err(ValidateError {
+                valid_up_to: self.len,
+                error_len: err.error_len().map(|len| len as u8),
+            }),
+        }
+    }
+
+    /// Checks that the string ends on a char boundary, returning its length.
+    ///
+    /// This is to be called once there is no more input, if the input is not
+    /// expected to end with a nul.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string ends in the middle of a multi-byte
+    /// sequence, or if an error was returned before.
+    #[inline]
+    pub fn finish(&self) -> Result<usize, ValidateError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        match self.partial_len {
+            0 => Ok(self.len),
+            _ => Err(ValidateError {
+                valid_up_to: self.len,
+                error_len: None,
+            }),
+        }
+    }
+}
+
+/// An error returned by a [`Validator`], indicating that a string is not
+/// valid UTF-8.
+///
+/// This is the same as a [`Utf8Error`](core::str::Utf8Error), but with
+/// positions relative to the start of the string rather than a chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidateError {
+    valid_up_to: usize,
+    error_len: Option<u8>,
+}
+
+impl ValidateError {
+    /// Returns the length of the valid prefix of the string.
+    #[inline]
+    #[must_use]
+    pub const fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Returns the length of the invalid sequence, or `None` if the string
+    /// ended in the middle of a multi-byte sequence.
+    ///
+    /// This behaves the same as [`Utf8Error::error_len`].
+    ///
+    /// [`Utf8Error::error_len`]: core::str::Utf8Error::error_len
+    #[inline]
+    #[must_use]
+    pub const fn error_len(&self) -> Option<usize> {
+        match self.error_len {
+            Some(len) => Some(len as usize),
+            None => None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn message(&self) -> &'static str {
+        msg!(InvalidUtf8)
+    }
+}
+
+impl fmt::Display for ValidateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+        core::write!(f, " at pos {}", self.valid_up_to)
+    }
+}
+
+impl core::error::Error for ValidateError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<ValidateError> for DStrError {
+    /// The [`Utf8Error`](core::str::Utf8Error) is not kept, since there is
+    /// none for a string validated in chunks.
+    #[inline]
+    fn from(value: ValidateError) -> Self {
+        DStrError::new(ErrorKind::InvalidUtf8, Some(value.valid_up_to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `input` split at every offset in `splits`, returning the
+    /// absolute position of the nul, or `None` if there is none.
+    fn feed(input: &[u8], splits: &[usize]) -> Result<Option<usize>, ValidateError> {
+        let mut validator = Validator::new();
+        let mut start = 0;
+
+        for end in splits.iter().copied().chain([input.len()]) {
+            match validator.feed(&input[start..end])? {
+                Status::Nul(pos) => {
+                    assert_eq!(validator.len(), start + pos);
+
+                    return Ok(Some(start + pos));
+                }
+                Status::Incomplete => assert!(!validator.is_empty()),
+                Status::Complete => assert_eq!(validator.len(), end),
+            }
+
+            start = end;
+        }
+
+        validator.finish().map(|_| None)
+    }
+
+    #[test]
+    fn split_at_every_offset() {
+        let input = "h\u{e9}llo w\u{f6}rld \u{1d11e} ok\0next".as_bytes();
+        let nul = input.iter().position(|&byte| byte == 0);
+
+        for first in 0..=input.len() {
+            for second in first..=input.len() {
+                assert_eq!(feed(input, &[first, second]), Ok(nul));
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_split_at_every_offset() {
+        let input = b"ab\xE2\x28\xA1\0";
+        let expected = ValidateError {
+            valid_up_to: 2,
+            error_len: Some(1),
+        };
+
+        for split in 0..=input.len() {
+            assert_eq!(feed(input, &[split]), Err(expected));
+        }
+    }
+
+    #[test]
+    fn truncated_sequence_at_the_end() {
+        let input = "ok \u{1d11e}".as_bytes();
+        let input = &input[..input.len() - 1];
+
+        for split in 0..=input.len() {
+            let err = feed(input, &[split]).unwrap_err();
+            assert_eq!((err.valid_up_to(), err.error_len()), (3, None));
+        }
+    }
+
+    #[test]
+    fn starts_over_after_a_nul() {
+        let mut validator = Validator::new();
+
+        assert_eq!(validator.feed(b"ab\0"), Ok(Status::Nul(2)));
+        assert_eq!(validator.len(), 2);
+        assert_eq!(validator.feed(b"\xC3"), Ok(Status::Incomplete));
+        assert_eq!(validator.feed(b"\xA9z\0"), Ok(Status::Nul(2)));
+        assert_eq!(validator.len(), 3);
+    }
+}
@@ -0,0 +1,41 @@
+use core::borrow::Borrow;
+
+use std::io::{self, Write};
+
+use crate::DStr;
+
+/// An extension trait for writing nul-terminated UTF-8 strings to a
+/// [`Write`], the producing counterpart to [`BufReadExt`].
+///
+/// This is implemented for every [`Write`].
+///
+/// [`BufReadExt`]: crate::io::BufReadExt
+pub trait WriteExt: Write {
+    /// Writes `string` along with its nul terminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by [`Write::write_all`].
+    #[inline]
+    fn write_dstr(&mut self, string: &DStr) -> io::Result<()> {
+        self.write_all(string.as_bytes_with_nul())
+    }
+
+    /// Writes every string of `iter`, each along with its nul terminator, as
+    /// is expected by `xargs -0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by [`Write::write_all`], at which
+    /// point the strings before it have been written.
+    fn write_all_nul_delimited<I>(&mut self, iter: I) -> io::Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<DStr>,
+    {
+        iter.into_iter()
+            .try_for_each(|string| self.write_dstr(string.borrow()))
+    }
+}
+
+impl<W: Write + ?Sized> WriteExt for W {}
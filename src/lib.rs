@@ -3,22 +3,146 @@
 //! A small, dumb set of tools for UTF-8 null terminated strings.
 
 #![no_std]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+// Keeps LLVM from turning the shims into calls to themselves.
+#![cfg_attr(feature = "c-shims", no_builtins)]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-pub(crate) mod mem;
+pub mod mem;
+
+#[cfg(feature = "c-shims")]
+pub mod c_shims;
+
+pub mod pattern;
 
 pub mod dstr;
 
 #[doc(inline)]
 pub use dstr::DStr;
 
+pub mod dstr_list;
+
+#[doc(inline)]
+pub use dstr_list::DStrList;
+
+pub mod dstr_block;
+
+#[doc(inline)]
+pub use dstr_block::DStrBlock;
+
+pub mod str_tab;
+
+#[doc(inline)]
+pub use str_tab::StrTab;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use str_tab::StrTabBuilder;
+
+pub mod argv;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use argv::ArgvBuilder;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use argv::EnvpBuilder;
+
+pub mod thin_dstr;
+
+#[doc(inline)]
+pub use thin_dstr::ThinDStr;
+
+pub mod foreign_dstring;
+
+#[doc(inline)]
+pub use foreign_dstring::ForeignDString;
+
+pub mod wdstr;
+
+#[doc(inline)]
+pub use wdstr::WDStr;
+
+pub mod mdstr;
+
+#[doc(inline)]
+pub use mdstr::MDStr;
+
+pub mod darray_string;
+
+#[doc(inline)]
+pub use darray_string::DArrayString;
+
+pub mod dstr_writer;
+
+#[doc(inline)]
+pub use dstr_writer::DStrWriter;
+
+pub mod io;
+
 #[cfg(feature = "alloc")]
 pub mod dstring;
 
 #[cfg(feature = "alloc")]
 #[doc(inline)]
 pub use dstring::DString;
+
+#[cfg(feature = "alloc")]
+pub mod dsmall_string;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use dsmall_string::DSmallString;
+
+#[cfg(feature = "alloc")]
+pub mod thin_dbox;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use thin_dbox::ThinDBox;
+
+#[cfg(feature = "alloc")]
+pub mod arc_dstr;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use arc_dstr::ArcDStr;
+
+#[cfg(feature = "alloc")]
+pub mod wdstring;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use wdstring::WDString;
+
+#[cfg(feature = "alloc")]
+pub mod mdstring;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use mdstring::MDString;
+
+#[cfg(feature = "alloc")]
+pub mod dstr_arena;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use dstr_arena::DStrArena;
+
+#[cfg(feature = "alloc")]
+pub mod dstr_interner;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use dstr_interner::DStrInterner;
+
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+pub mod proc;
+
+#[cfg(feature = "std")]
+pub mod env;
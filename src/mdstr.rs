@@ -0,0 +1,344 @@
+//! Module for borrowed nul-terminated Modified UTF-8 strings.
+//!
+//! Modified UTF-8 is the encoding used by the JNI, such as for
+//! `GetStringUTFChars`. It differs from UTF-8 in two ways:
+//!
+//! - `U+0000` is encoded as `C0 80`, so the only nul byte is the terminator.
+//!
+//! - Characters outside of the basic multilingual plane are encoded as a
+//!   UTF-16 surrogate pair, with each surrogate taking three bytes.
+
+use core::{
+    ffi::c_char,
+    fmt::{self, Write},
+    iter::FusedIterator,
+    num::NonZeroUsize,
+    slice::from_raw_parts,
+};
+
+mod error;
+pub use error::*;
+
+#[cfg(feature = "alloc")]
+use crate::{dstr::NulError, DString};
+use crate::{mem, DStr};
+
+/// A nul-terminated Modified UTF-8 string, as used by the JNI.
+///
+/// Any string can be represented losslessly, including ones with nuls, so
+/// converting to and from a [`str`] never fails.
+///
+/// # Safety
+///
+/// - The internal representation must always be valid Modified UTF-8, with
+///   no overlong encodings other than `C0 80`, and no unpaired surrogates.
+///
+/// - There must be no internal nul bytes, and the last byte is always the nul
+///   terminator.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct MDStr {
+    raw: [u8],
+}
+
+impl MDStr {
+    /// An empty [`MDStr`], containing only the nul terminator.
+    pub const EMPTY: &'static MDStr = MDStr::from_bytes_with_nul(&[0]);
+
+    /// Returns the length of this [`MDStr`] in bytes, including the nul
+    /// terminator.
+    #[inline]
+    #[must_use]
+    pub const fn len_with_nul(&self) -> NonZeroUsize {
+        debug_assert!(
+            !self.raw.is_empty(),
+            "a nul-terminated string must have at least a nul"
+        );
+
+        // SAFETY: A nul-terminated string always has at least the nul.
+        unsafe { NonZeroUsize::new_unchecked(self.raw.len()) }
+    }
+
+    /// Returns the length of this [`MDStr`] in bytes, excluding the nul
+    /// terminator.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len_with_nul().get() - 1
+    }
+
+    /// Returns whether this [`MDStr`] is empty.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        // SAFETY: A nul-terminated string always contains at least the nul.
+        unsafe { *self.raw.as_ptr() == 0 }
+    }
+
+    /// Converts this [`MDStr`] to a byte slice containing the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes_with_nul(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Converts this [`MDStr`] to a byte slice excluding the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        // SAFETY: The first `len` bytes are always in bounds.
+        unsafe { from_raw_parts(self.raw.as_ptr(), self.len()) }
+    }
+
+    /// Returns a raw pointer to the start of this [`MDStr`].
+    #[inline]
+    #[must_use]
+    pub const fn as_ptr(&self) -> *const u8 {
+        self.raw.as_ptr()
+    }
+
+    /// Returns a raw C pointer to the start of this [`MDStr`].
+    #[inline]
+    #[must_use]
+    pub const fn as_c_ptr(&self) -> *const c_char {
+        self.as_ptr().cast()
+    }
+
+    /// Returns this string as a [`DStr`], if its encoding is the same as in
+    /// UTF-8.
+    ///
+    /// This is the case when the string contains no nuls, and no characters
+    /// outside of the basic multilingual plane.
+    #[inline]
+    #[must_use]
+    pub const fn as_dstr(&self) -> Option<&DStr> {
+        match core::str::from_utf8(&self.raw) {
+            // SAFETY: The only nul byte in an `MDStr` is the terminator.
+            Ok(_) => Some(unsafe { DStr::from_bytes_with_nul_unchecked(&self.raw) }),
+            Err(_) => None,
+        }
+    }
+
+    /// Create an [`MDStr`] from a nul-terminated byte slice without doing any
+    /// checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the input is nul-terminated Modified UTF-8
+    /// with no interior nul bytes.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn from_bytes_with_nul_unchecked(bytes: &[u8]) -> &MDStr {
+        // SAFETY: `MDStr` is a transparent wrapper around `[u8]`, and the
+        //         caller ensures the bytes are a valid `MDStr`.
+        unsafe { &*(bytes as *const [u8] as *const MDStr) }
+    }
+
+    /// Try to create an [`MDStr`] from a nul-terminated Modified UTF-8 byte
+    /// slice.
+    ///
+    /// Returns an error if the bytes are not nul-terminated, contain interior
+    /// nuls, or are not valid Modified UTF-8, in which case the position is
+    /// that of the offending byte.
+    #[inline]
+    pub const fn try_from_bytes_with_nul(bytes: &[u8]) -> Result<&MDStr, FromModifiedError> {
+        let len = bytes.len();
+        let mut idx = 0;
+
+        while idx < len {
+            let width = match bytes[idx] {
+                0 if idx + 1 == len => {
+                    // SAFETY: The only nul is the terminator, and every
+                    //         sequence before it was valid.
+                    return Ok(unsafe { MDStr::from_bytes_with_nul_unchecked(bytes) });
+                }
+                0 => return Err(FromModifiedError::InteriorNul(idx)),
+                0x01..=0x7F => 1,
+                0xC0 if idx + 1 < len && bytes[idx + 1] == 0x80 => 2,
+                0xC2..=0xDF if idx + 1 < len && is_continuation(bytes[idx + 1]) => 2,
+                0xE0..=0xEF => match decode_unit(bytes, idx) {
+                    Some(0xD800..=0xDBFF) => match decode_unit(bytes, idx + 3) {
+                        Some(0xDC00..=0xDFFF) => 6,
+                        _ => return Err(FromModifiedError::InvalidModifiedUtf8(idx)),
+                    },
+                    Some(0xDC00..=0xDFFF) | None => {
+                        return Err(FromModifiedError::InvalidModifiedUtf8(idx))
+                    }
+                    Some(_) => 3,
+                },
+                _ => return Err(FromModifiedError::InvalidModifiedUtf8(idx)),
+            };
+
+            idx += width;
+        }
+
+        Err(FromModifiedError::NotNulTerminated)
+    }
+
+    /// Create an [`MDStr`] from a nul-terminated Modified UTF-8 byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is not nul-terminated, contains interior nul bytes,
+    /// or is not valid Modified UTF-8.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_bytes_with_nul(bytes: &[u8]) -> &MDStr {
+        match MDStr::try_from_bytes_with_nul(bytes) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to create an [`MDStr`] from a raw pointer, such as one returned by
+    /// `GetStringUTFChars`.
+    ///
+    /// Returns an error if the string is not valid Modified UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the following:
+    ///
+    /// - `ptr` is not null, and points to a nul-terminated string.
+    ///
+    /// - The nul is within [`isize::MAX`] bytes from `ptr`.
+    ///
+    /// - The string is not mutated or deallocated for the lifetime `'a`.
+    #[inline]
+    pub const unsafe fn from_ptr<'a>(ptr: *const c_char) -> Result<&'a MDStr, FromModifiedError> {
+        // SAFETY: The caller ensures `ptr` points to a nul-terminated string.
+        let len = unsafe { mem::find_nul(ptr) };
+
+        // SAFETY: The string is `len + 1` bytes long, including the nul.
+        MDStr::try_from_bytes_with_nul(unsafe { from_raw_parts(ptr.cast::<u8>(), len + 1) })
+    }
+
+    /// Returns an iterator over the [`char`]s of this [`MDStr`], excluding the
+    /// nul terminator.
+    #[inline]
+    pub fn chars(&self) -> Chars<'_> {
+        Chars {
+            bytes: self.as_bytes(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl MDStr {
+    /// Converts this [`MDStr`] to a UTF-8 [`DString`].
+    ///
+    /// Returns an error if the string contains a nul, with the byte position
+    /// of the encoded nul within this [`MDStr`].
+    pub fn to_dstring(&self) -> Result<DString, NulError> {
+        // `C0` is only ever used to encode a nul.
+        if let Some(pos) = mem::find_byte(0xC0, self.as_bytes()) {
+            return Err(NulError::new(pos));
+        }
+
+        let mut string = DString::with_capacity(self.len());
+        string.extend(self.chars());
+
+        Ok(string)
+    }
+}
+
+impl fmt::Debug for MDStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+
+        for ch in self.chars() {
+            for esc in ch.escape_debug() {
+                f.write_char(esc)?;
+            }
+        }
+
+        f.write_char('"')
+    }
+}
+
+impl fmt::Display for MDStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ch in self.chars() {
+            f.write_char(ch)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns whether `byte` is a UTF-8 continuation byte.
+#[inline(always)]
+const fn is_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
+/// Decodes the three byte sequence at `idx`, returning `None` if it is invalid
+/// or overlong.
+#[inline]
+const fn decode_unit(bytes: &[u8], idx: usize) -> Option<u16> {
+    if idx + 2 >= bytes.len() {
+        return None;
+    }
+
+    let (a, b, c) = (bytes[idx], bytes[idx + 1], bytes[idx + 2]);
+
+    if a & 0xF0 != 0xE0 || !is_continuation(b) || !is_continuation(c) {
+        return None;
+    }
+
+    let unit = ((a as u16 & 0x0F) << 12) | ((b as u16 & 0x3F) << 6) | (c as u16 & 0x3F);
+
+    if unit < 0x800 {
+        None
+    } else {
+        Some(unit)
+    }
+}
+
+/// An iterator over the [`char`]s of an [`MDStr`].
+///
+/// This is created by [`MDStr::chars`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Chars<'a> {
+    bytes: &'a [u8],
+}
+
+impl Iterator for Chars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let bytes = self.bytes;
+        let first = *bytes.first()?;
+
+        // The bytes are always valid Modified UTF-8, so each sequence is
+        // complete.
+        let (code, width) = match first {
+            0x00..=0x7F => (first as u32, 1),
+            0xC0..=0xDF => (((first as u32 & 0x1F) << 6) | (bytes[1] as u32 & 0x3F), 2),
+            _ => match decode_unit(bytes, 0) {
+                Some(high @ 0xD800..=0xDBFF) => {
+                    let low = decode_unit(bytes, 3).unwrap_or(0xDC00);
+                    let code = 0x10000 + ((high as u32 - 0xD800) << 10) + (low as u32 - 0xDC00);
+
+                    (code, 6)
+                }
+                unit => (unit.unwrap_or(0xFFFD) as u32, 3),
+            },
+        };
+
+        self.bytes = &bytes[width..];
+
+        Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.len();
+
+        (len.div_ceil(6), Some(len))
+    }
+}
+
+impl FusedIterator for Chars<'_> {}
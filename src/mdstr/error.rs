@@ -0,0 +1,67 @@
+use core::fmt;
+
+use crate::dstr::{msg, DStrError, ErrorKind};
+
+/// An error indicating that a byte slice is not a valid [`MDStr`].
+///
+/// [`MDStr`]: crate::MDStr
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromModifiedError {
+    NotNulTerminated,
+    InteriorNul(usize),
+    InvalidModifiedUtf8(usize),
+}
+
+impl FromModifiedError {
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            FromModifiedError::NotNulTerminated => msg!(NotNulTerminated),
+            FromModifiedError::InteriorNul(_) => msg!(InteriorNul),
+            FromModifiedError::InvalidModifiedUtf8(_) => msg!(InvalidModifiedUtf8),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for FromModifiedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        if let &Self::InteriorNul(pos) | &Self::InvalidModifiedUtf8(pos) = self {
+            core::write!(f, " at pos {pos}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for FromModifiedError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<FromModifiedError> for DStrError {
+    #[inline]
+    fn from(value: FromModifiedError) -> Self {
+        match value {
+            FromModifiedError::NotNulTerminated => {
+                DStrError::new(ErrorKind::NotNulTerminated, None)
+            }
+            FromModifiedError::InteriorNul(pos) => {
+                DStrError::new(ErrorKind::InteriorNul, Some(pos))
+            }
+            FromModifiedError::InvalidModifiedUtf8(pos) => {
+                DStrError::new(ErrorKind::InvalidModifiedUtf8, Some(pos))
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! Module for owned nul-terminated Modified UTF-8 strings.
+
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{mdstr::FromModifiedError, DStr, DString, MDStr};
+
+/// An owned nul-terminated Modified UTF-8 string, as used by the JNI.
+///
+/// This is the Modified UTF-8 counterpart of a [`DString`]. Since nuls are
+/// encoded as `C0 80`, pushing a string never fails.
+///
+/// # Safety
+///
+/// The buffer must always uphold the invariants of an [`MDStr`].
+pub struct MDString {
+    inner: Vec<u8>,
+}
+
+impl MDString {
+    /// Creates a new, empty [`MDString`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> MDString {
+        MDString { inner: vec![0] }
+    }
+
+    /// Creates a new, empty [`MDString`] with room for at least `capacity`
+    /// bytes, excluding the nul terminator.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> MDString {
+        let mut inner = Vec::with_capacity(capacity.saturating_add(1));
+        inner.push(0);
+
+        MDString { inner }
+    }
+
+    /// Create an [`MDString`] from a nul-terminated Modified UTF-8 buffer
+    /// without doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `vec` is nul-terminated Modified UTF-8 with
+    /// no interior nul bytes.
+    #[inline]
+    #[must_use]
+    pub unsafe fn from_vec_with_nul_unchecked(vec: Vec<u8>) -> MDString {
+        MDString { inner: vec }
+    }
+
+    /// Try to create an [`MDString`] from a nul-terminated Modified UTF-8
+    /// buffer.
+    #[inline]
+    pub fn try_from_vec_with_nul(vec: Vec<u8>) -> Result<MDString, FromModifiedError> {
+        MDStr::try_from_bytes_with_nul(&vec)?;
+
+        // SAFETY: We just checked that `vec` is a valid `MDStr`.
+        Ok(unsafe { MDString::from_vec_with_nul_unchecked(vec) })
+    }
+
+    /// Returns the capacity of this [`MDString`] in bytes, excluding the nul
+    /// terminator.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity() - 1
+    }
+
+    /// Reserves room for at least `additional` more bytes.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional);
+    }
+
+    /// Appends `ch` to the end of this [`MDString`], before the nul
+    /// terminator.
+    #[inline]
+    pub fn push(&mut self, ch: char) {
+        self.inner.pop();
+        encode(ch, &mut self.inner);
+        self.inner.push(0);
+    }
+
+    /// Appends `string` to the end of this [`MDString`], before the nul
+    /// terminator.
+    pub fn push_str(&mut self, string: &str) {
+        self.inner.pop();
+        self.inner.reserve(string.len() + 1);
+
+        for ch in string.chars() {
+            encode(ch, &mut self.inner);
+        }
+
+        self.inner.push(0);
+    }
+
+    /// Appends `string` to the end of this [`MDString`], before the nul
+    /// terminator.
+    #[inline]
+    pub fn push_dstr(&mut self, string: &DStr) {
+        self.push_str(string.as_str());
+    }
+
+    /// Appends `string` to the end of this [`MDString`], before the nul
+    /// terminator.
+    #[inline]
+    pub fn push_mdstr(&mut self, string: &MDStr) {
+        self.inner.pop();
+        self.inner.extend_from_slice(string.as_bytes_with_nul());
+    }
+
+    /// Removes all contents of this [`MDString`], keeping the capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.inner.push(0);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_mdstr(&self) -> &MDStr {
+        // SAFETY: The buffer always upholds the invariants of an `MDStr`.
+        unsafe { MDStr::from_bytes_with_nul_unchecked(&self.inner) }
+    }
+
+    /// Consumes this [`MDString`], returning the buffer including the nul
+    /// terminator.
+    #[inline]
+    #[must_use]
+    pub fn into_bytes_with_nul(self) -> Vec<u8> {
+        self.inner
+    }
+}
+
+/// Appends the Modified UTF-8 encoding of `ch` to `buf`.
+#[inline]
+fn encode(ch: char, buf: &mut Vec<u8>) {
+    match ch {
+        '\0' => buf.extend_from_slice(&[0xC0, 0x80]),
+        '\u{1}'..='\u{FFFF}' => buf.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+        _ => {
+            for unit in ch.encode_utf16(&mut [0; 2]) {
+                buf.extend_from_slice(&[
+                    0xE0 | (*unit >> 12) as u8,
+                    0x80 | (*unit >> 6 & 0x3F) as u8,
+                    0x80 | (*unit & 0x3F) as u8,
+                ]);
+            }
+        }
+    }
+}
+
+impl Default for MDString {
+    #[inline]
+    fn default() -> Self {
+        MDString::new()
+    }
+}
+
+impl Clone for MDString {
+    #[inline]
+    fn clone(&self) -> Self {
+        MDString {
+            inner: self.inner.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+    }
+}
+
+impl PartialEq for MDString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_mdstr() == other.as_mdstr()
+    }
+}
+
+impl Eq for MDString {}
+
+impl PartialOrd for MDString {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MDString {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_mdstr().cmp(other.as_mdstr())
+    }
+}
+
+impl Hash for MDString {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_mdstr().hash(state)
+    }
+}
+
+impl fmt::Debug for MDString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_mdstr(), f)
+    }
+}
+
+impl fmt::Display for MDString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_mdstr(), f)
+    }
+}
+
+impl fmt::Write for MDString {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+impl Deref for MDString {
+    type Target = MDStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_mdstr()
+    }
+}
+
+impl From<&MDStr> for MDString {
+    #[inline]
+    fn from(value: &MDStr) -> Self {
+        // SAFETY: The bytes are copied from a valid `MDStr`.
+        unsafe { MDString::from_vec_with_nul_unchecked(value.as_bytes_with_nul().to_vec()) }
+    }
+}
+
+impl From<&str> for MDString {
+    #[inline]
+    fn from(value: &str) -> Self {
+        let mut string = MDString::with_capacity(value.len());
+        string.push_str(value);
+        string
+    }
+}
+
+impl From<&DStr> for MDString {
+    #[inline]
+    fn from(value: &DStr) -> Self {
+        MDString::from(value.as_str())
+    }
+}
+
+impl From<&DString> for MDString {
+    #[inline]
+    fn from(value: &DString) -> Self {
+        MDString::from(value.as_str())
+    }
+}
+
+impl From<&MDStr> for String {
+    #[inline]
+    fn from(value: &MDStr) -> Self {
+        value.chars().collect()
+    }
+}
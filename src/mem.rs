@@ -1,11 +1,57 @@
-use core::ffi::c_char;
+//! Module for `const` friendly byte string primitives.
+//!
+//! These are the building blocks of the checks done by this crate, and can be
+//! used in `const` contexts, unlike their counterparts on slices.
 
-/// `const` friendly memchr function.
+use core::{
+    cmp::Ordering,
+    ffi::{c_char, CStr},
+    str::{from_utf8, Utf8Error},
+};
+
+#[cfg(feature = "simd")]
+mod simd;
+
+/// The size of a word in bytes.
+const WORD: usize = core::mem::size_of::<usize>();
+
+/// A word with every byte set to `0x01`.
+const LO: usize = usize::MAX / 0xFF;
+
+/// A word with every byte set to `0x80`.
+const HI: usize = LO << 7;
+
+/// Returns whether any byte of `word` is zero.
 #[inline(always)]
-#[no_mangle]
+const fn has_zero(word: usize) -> bool {
+    word.wrapping_sub(LO) & !word & HI != 0
+}
+
+/// `const` friendly memchr function.
+///
+/// This scans a word at a time, and finds the exact position within the
+/// first word that contains `needle` one byte at a time.
 pub const fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    let repeated = LO.wrapping_mul(needle as usize);
     let mut index = 0usize;
 
+    while index + WORD <= haystack.len() {
+        // SAFETY: The word lies within the haystack.
+        let word = unsafe {
+            haystack
+                .as_ptr()
+                .add(index)
+                .cast::<usize>()
+                .read_unaligned()
+        };
+
+        if has_zero(word ^ repeated) {
+            break;
+        }
+
+        index += WORD;
+    }
+
     while index < haystack.len() {
         if haystack[index] == needle {
             return Some(index);
@@ -17,8 +63,228 @@ pub const fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
     None
 }
 
+/// `const` friendly fused UTF-8 validation and nul search.
+///
+/// This validates `bytes` up to the first nul in a single pass, returning the
+/// position of the nul, or `None` if there is none and all of `bytes` is valid
+/// UTF-8. Returns the length of the valid prefix as an error if invalid UTF-8
+/// comes before any nul.
+#[inline]
+pub const fn validate_until_nul(bytes: &[u8]) -> Result<Option<usize>, usize> {
+    scan_until_nul(bytes, 0)
+}
+
+/// The same as [`validate_until_nul`], but starting at `start`, which must lie
+/// on a char boundary. Positions are relative to all of `bytes`.
+pub(crate) const fn scan_until_nul(bytes: &[u8], start: usize) -> Result<Option<usize>, usize> {
+    let len = bytes.len();
+    let mut index = start;
+
+    while index < len {
+        // Skip whole words of non-nul ASCII.
+        if index + WORD <= len {
+            // SAFETY: The word lies within `bytes`.
+            let word = unsafe { bytes.as_ptr().add(index).cast::<usize>().read_unaligned() };
+
+            if word & HI == 0 && !has_zero(word) {
+                index += WORD;
+                continue;
+            }
+        }
+
+        let first = bytes[index];
+
+        if first == 0 {
+            return Ok(Some(index));
+        }
+
+        if first < 0x80 {
+            index += 1;
+            continue;
+        }
+
+        // The range of the second byte, which rules out overlong encodings,
+        // surrogates, and code points past `U+10FFFF`.
+        let (width, lo, hi) = match first {
+            0xC2..=0xDF => (2, 0x80, 0xBF),
+            0xE0 => (3, 0xA0, 0xBF),
+            0xE1..=0xEC | 0xEE..=0xEF => (3, 0x80, 0xBF),
+            0xED => (3, 0x80, 0x9F),
+            0xF0 => (4, 0x90, 0xBF),
+            0xF1..=0xF3 => (4, 0x80, 0xBF),
+            0xF4 => (4, 0x80, 0x8F),
+            _ => return Err(index),
+        };
+
+        if index + width > len || bytes[index + 1] < lo || bytes[index + 1] > hi {
+            return Err(index);
+        }
+
+        let mut offset = 2;
+
+        while offset < width {
+            if bytes[index + offset] & 0xC0 != 0x80 {
+                return Err(index);
+            }
+
+            offset += 1;
+        }
+
+        index += width;
+    }
+
+    Ok(None)
+}
+
+/// Returns the error of validating `bytes`, which must not be valid UTF-8.
+#[inline]
+pub(crate) const fn utf8_error(bytes: &[u8]) -> Utf8Error {
+    match from_utf8(bytes) {
+        Ok(_) => panic!("the bytes were expected to be invalid UTF-8"),
+        Err(err) => err,
+    }
+}
+
+/// `const` friendly memrchr function, which finds the last occurrence of
+/// `needle`.
+#[must_use]
+pub const fn memrchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    let mut index = haystack.len();
+
+    while index > 0 {
+        index -= 1;
+
+        if haystack[index] == needle {
+            return Some(index);
+        }
+    }
+
+    None
+}
+
+/// `const` friendly memcmp function.
+///
+/// Unlike the C function, the slices may differ in length, in which case they
+/// are compared the same way as [`Ord`] for slices.
+#[must_use]
+pub const fn memcmp(a: &[u8], b: &[u8]) -> Ordering {
+    let mut index = 0usize;
+
+    while index < a.len() && index < b.len() {
+        if a[index] != b[index] {
+            return cmp_usize(a[index] as usize, b[index] as usize);
+        }
+
+        index += 1;
+    }
+
+    cmp_usize(a.len(), b.len())
+}
+
+#[inline(always)]
+const fn cmp_usize(a: usize, b: usize) -> Ordering {
+    if a < b {
+        Ordering::Less
+    } else if a > b {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// `const` friendly memmem function, which finds the first occurrence of
+/// `needle` within `haystack`.
+///
+/// An empty needle is found at the start.
+#[must_use]
+pub const fn memmem(needle: &[u8], haystack: &[u8]) -> Option<usize> {
+    let [first, ..] = needle else {
+        return Some(0);
+    };
+
+    let mut start = 0usize;
+
+    while start + needle.len() <= haystack.len() {
+        // SAFETY: `start` is in bounds.
+        let rest = unsafe {
+            core::slice::from_raw_parts(haystack.as_ptr().add(start), haystack.len() - start)
+        };
+
+        // Skip ahead to the next candidate.
+        start += match memchr(*first, rest) {
+            Some(offset) => offset,
+            None => return None,
+        };
+
+        if start + needle.len() > haystack.len() {
+            return None;
+        }
+
+        let mut index = 1usize;
+
+        while index < needle.len() && haystack[start + index] == needle[index] {
+            index += 1;
+        }
+
+        if index == needle.len() {
+            return Some(start);
+        }
+
+        start += 1;
+    }
+
+    None
+}
+
+/// Runtime memchr function.
+///
+/// With the `memchr` feature, this uses the SIMD implementation of the
+/// `memchr` crate. Otherwise, with the `simd` feature, this uses SSE2 or NEON
+/// where enabled. Otherwise it is the same as [`memchr`].
+#[inline]
+#[must_use]
+pub fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    {
+        ::memchr::memchr(needle, haystack)
+    }
+
+    #[cfg(all(feature = "simd", not(feature = "memchr")))]
+    {
+        simd::find_byte(needle, haystack)
+    }
+
+    #[cfg(not(any(feature = "simd", feature = "memchr")))]
+    {
+        memchr(needle, haystack)
+    }
+}
+
+/// Runtime UTF-8 validation function.
+///
+/// This behaves the same as [`core::str::from_utf8`]. With the `simd` feature
+/// the ASCII prefix of `bytes` is skipped with SSE2 or NEON where enabled.
+#[inline]
+pub fn validate_utf8(bytes: &[u8]) -> Result<&str, Utf8Error> {
+    #[cfg(feature = "simd")]
+    {
+        let ascii = simd::ascii_prefix(bytes);
+
+        if from_utf8(&bytes[ascii..]).is_ok() {
+            // SAFETY: The prefix is ASCII, and the rest is valid UTF-8.
+            return Ok(unsafe { core::str::from_utf8_unchecked(bytes) });
+        }
+    }
+
+    // This also reports the position of an error relative to all of `bytes`.
+    from_utf8(bytes)
+}
+
 /// `const` friendly strlen function.
 ///
+/// This reads one byte at a time, including at runtime, so it never calls
+/// into the C library. Prefer [`find_nul`] otherwise.
+///
 /// # Safety
 ///
 /// The caller must ensure that the input pointer is a valid
@@ -37,3 +303,251 @@ pub const unsafe fn strlen(ptr: *const c_char) -> usize {
 
     len
 }
+
+/// Fast strlen function.
+///
+/// At runtime this calls `strlen` of the C library through
+/// [`CStr::from_ptr`], which is vectorized on most platforms, while in a
+/// `const` context it reads one byte at a time like [`strlen`].
+///
+/// # Safety
+///
+/// The caller must ensure that the input pointer is a valid
+/// pointer to a null terminated string.
+///
+/// Additionally the null must be within [`isize::MAX`] bytes from
+/// `ptr`.
+#[inline]
+#[must_use]
+pub const unsafe fn find_nul(ptr: *const c_char) -> usize {
+    // SAFETY: The caller ensures `ptr` points to a nul-terminated string.
+    unsafe { CStr::from_ptr(ptr) }.count_bytes()
+}
+
+/// `const` friendly wcslen function, for nul-terminated UTF-16 strings.
+///
+/// # Safety
+///
+/// The caller must ensure that the input pointer is a valid
+/// pointer to a null terminated string.
+///
+/// Additionally the null must be within [`isize::MAX`] bytes from
+/// `ptr`.
+#[inline(always)]
+#[must_use]
+pub const unsafe fn wcslen(ptr: *const u16) -> usize {
+    let mut len = 0usize;
+
+    while unsafe { *ptr.add(len) } != 0 {
+        len += 1;
+    }
+
+    len
+}
+
+/// `const` friendly strcmp function.
+///
+/// Bytes are compared as unsigned, the same as the C function.
+///
+/// # Safety
+///
+/// The caller must ensure that both pointers are valid pointers to
+/// null terminated strings.
+#[inline]
+#[must_use]
+pub const unsafe fn strcmp(a: *const c_char, b: *const c_char) -> Ordering {
+    unsafe { compare(a, b, usize::MAX, false) }
+}
+
+/// `const` friendly strncmp function, which compares at most `n` bytes.
+///
+/// Bytes are compared as unsigned, the same as the C function.
+///
+/// # Safety
+///
+/// The caller must ensure that both pointers are valid pointers to
+/// null terminated strings, or to at least `n` readable bytes.
+#[inline]
+#[must_use]
+pub const unsafe fn strncmp(a: *const c_char, b: *const c_char, n: usize) -> Ordering {
+    unsafe { compare(a, b, n, false) }
+}
+
+/// `const` friendly strcasecmp function, which ignores ASCII case.
+///
+/// Bytes are compared as unsigned after being lowercased, the same as the C
+/// function in the `C` locale.
+///
+/// # Safety
+///
+/// The caller must ensure that both pointers are valid pointers to
+/// null terminated strings.
+#[inline]
+#[must_use]
+pub const unsafe fn strcasecmp(a: *const c_char, b: *const c_char) -> Ordering {
+    unsafe { compare(a, b, usize::MAX, true) }
+}
+
+/// `const` friendly strncasecmp function, which ignores ASCII case and
+/// compares at most `n` bytes.
+///
+/// # Safety
+///
+/// The caller must ensure that both pointers are valid pointers to
+/// null terminated strings, or to at least `n` readable bytes.
+#[inline]
+#[must_use]
+pub const unsafe fn strncasecmp(a: *const c_char, b: *const c_char, n: usize) -> Ordering {
+    unsafe { compare(a, b, n, true) }
+}
+
+/// `const` friendly strverscmp function, which compares runs of digits by
+/// their numeric value.
+///
+/// This follows the semantics of glibc, where a run of digits with leading
+/// zeros is treated as a fractional part, which sorts before any integer
+/// part, and the same as `ls -v`.
+///
+/// # Safety
+///
+/// The caller must ensure that both pointers are valid pointers to
+/// null terminated strings.
+#[must_use]
+pub const unsafe fn strverscmp(a: *const c_char, b: *const c_char) -> Ordering {
+    // The states, each being an offset into the tables below.
+    const NORMAL: u8 = 0;
+    const INTEGER: u8 = 3;
+    const FRACTION: u8 = 6;
+    const ZEROS: u8 = 9;
+
+    // The results that are not decided by the tables alone.
+    const CMP: i8 = 2;
+    const LEN: i8 = 3;
+
+    // The next state when the current bytes are equal, indexed by the state
+    // and the class of the byte.
+    #[rustfmt::skip]
+    const NEXT: [u8; 12] = [
+        // other   digit     zero
+        NORMAL,    INTEGER,  ZEROS,    // NORMAL
+        NORMAL,    INTEGER,  INTEGER,  // INTEGER
+        NORMAL,    FRACTION, FRACTION, // FRACTION
+        NORMAL,    FRACTION, ZEROS,    // ZEROS
+    ];
+
+    // The result once the bytes differ, indexed by the state and the classes
+    // of both bytes.
+    #[rustfmt::skip]
+    const RESULT: [i8; 36] = [
+        // x/x x/d  x/0  d/x  d/d  d/0  0/x  0/d  0/0
+        CMP,   CMP, CMP, CMP, LEN, CMP, CMP, CMP, CMP, // NORMAL
+        CMP,   -1,  -1,  1,   LEN, LEN, 1,   LEN, LEN, // INTEGER
+        CMP,   CMP, CMP, CMP, CMP, CMP, CMP, CMP, CMP, // FRACTION
+        CMP,   1,   1,   -1,  CMP, CMP, -1,  CMP, CMP, // ZEROS
+    ];
+
+    /// Returns `0` for a non-digit, `1` for a non-zero digit, and `2` for a
+    /// zero.
+    #[inline(always)]
+    const fn class(byte: u8) -> u8 {
+        (byte == b'0') as u8 + byte.is_ascii_digit() as u8
+    }
+
+    let (a, b) = (a.cast::<u8>(), b.cast::<u8>());
+    let mut index = 0usize;
+
+    // SAFETY: Neither string is read past its nul terminator.
+    let (mut x, mut y) = unsafe { (*a, *b) };
+    let mut state = NORMAL + class(x);
+
+    while x == y {
+        if x == 0 {
+            return Ordering::Equal;
+        }
+
+        state = NEXT[state as usize];
+        index += 1;
+
+        // SAFETY: Neither string has ended yet.
+        (x, y) = unsafe { (*a.add(index), *b.add(index)) };
+        state += class(x);
+    }
+
+    let diff = cmp_usize(x as usize, y as usize);
+
+    match RESULT[state as usize * 3 + class(y) as usize] {
+        CMP => diff,
+        LEN => {
+            // The longer run of digits is the larger number.
+            loop {
+                index += 1;
+
+                // SAFETY: Neither string is read past its nul terminator, as
+                //         the nul is not a digit.
+                let (x, y) = unsafe { (*a.add(index), *b.add(index)) };
+
+                match (x.is_ascii_digit(), y.is_ascii_digit()) {
+                    (true, true) => {}
+                    (true, false) => return Ordering::Greater,
+                    (false, true) => return Ordering::Less,
+                    (false, false) => return diff,
+                }
+            }
+        }
+        -1 => Ordering::Less,
+        _ => Ordering::Greater,
+    }
+}
+
+/// # Safety
+///
+/// See [`strncmp`].
+#[inline(always)]
+const unsafe fn compare(a: *const c_char, b: *const c_char, n: usize, fold: bool) -> Ordering {
+    let mut index = 0usize;
+
+    while index < n {
+        let (mut x, mut y) = unsafe { (*a.add(index) as u8, *b.add(index) as u8) };
+
+        if fold {
+            x = x.to_ascii_lowercase();
+            y = y.to_ascii_lowercase();
+        }
+
+        if x != y {
+            return cmp_usize(x as usize, y as usize);
+        }
+
+        if x == 0 {
+            break;
+        }
+
+        index += 1;
+    }
+
+    Ordering::Equal
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn find_nul_every_alignment() {
+        for offset in 0..2 * WORD {
+            for len in 0..4 * WORD {
+                // Allocate exactly the string, so reading past the nul is
+                // caught by Miri.
+                let mut buf = vec![b'a'; offset + len + 1].into_boxed_slice();
+                buf[offset + len] = 0;
+
+                let ptr = unsafe { buf.as_ptr().add(offset) }.cast::<c_char>();
+
+                assert_eq!(unsafe { find_nul(ptr) }, len);
+                assert_eq!(unsafe { strlen(ptr) }, len);
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Hand-written SIMD searches, selected at compile time.
+//!
+//! Every function falls back to the word-at-a-time version on targets without
+//! a supported instruction set.
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+mod imp {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    const LANES: usize = 16;
+
+    // With the `memchr` feature, `mem::find_byte` uses that crate instead.
+    #[cfg(not(feature = "memchr"))]
+    #[inline]
+    pub fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+        let mut index = 0usize;
+
+        // SAFETY: SSE2 is enabled.
+        let needles = unsafe { _mm_set1_epi8(needle as i8) };
+
+        while index + LANES <= haystack.len() {
+            // SAFETY: The chunk lies within the haystack, and SSE2 is enabled.
+            let mask = unsafe {
+                let chunk = _mm_loadu_si128(haystack.as_ptr().add(index).cast());
+                _mm_movemask_epi8(_mm_cmpeq_epi8(chunk, needles))
+            };
+
+            if mask != 0 {
+                return Some(index + mask.trailing_zeros() as usize);
+            }
+
+            index += LANES;
+        }
+
+        super::super::memchr(needle, &haystack[index..]).map(|offset| index + offset)
+    }
+
+    #[inline]
+    pub fn ascii_prefix(bytes: &[u8]) -> usize {
+        let mut index = 0usize;
+
+        while index + LANES <= bytes.len() {
+            // SAFETY: The chunk lies within the slice, and SSE2 is enabled.
+            let mask = unsafe {
+                let chunk = _mm_loadu_si128(bytes.as_ptr().add(index).cast());
+                _mm_movemask_epi8(chunk)
+            };
+
+            if mask != 0 {
+                return index + mask.trailing_zeros() as usize;
+            }
+
+            index += LANES;
+        }
+
+        index + super::scalar_ascii_prefix(&bytes[index..])
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod imp {
+    use core::arch::aarch64::*;
+
+    const LANES: usize = 16;
+
+    // With the `memchr` feature, `mem::find_byte` uses that crate instead.
+    #[cfg(not(feature = "memchr"))]
+    #[inline]
+    pub fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+        let mut index = 0usize;
+
+        // SAFETY: NEON is enabled.
+        let needles = unsafe { vdupq_n_u8(needle) };
+
+        while index + LANES <= haystack.len() {
+            // SAFETY: The chunk lies within the haystack, and NEON is enabled.
+            let mask = unsafe {
+                let chunk = vld1q_u8(haystack.as_ptr().add(index));
+                let eq = vceqq_u8(chunk, needles);
+
+                // Narrowing leaves four bits for every lane.
+                let narrowed = vshrn_n_u16::<4>(vreinterpretq_u16_u8(eq));
+                vget_lane_u64::<0>(vreinterpret_u64_u8(narrowed))
+            };
+
+            if mask != 0 {
+                return Some(index + mask.trailing_zeros() as usize / 4);
+            }
+
+            index += LANES;
+        }
+
+        super::super::memchr(needle, &haystack[index..]).map(|offset| index + offset)
+    }
+
+    #[inline]
+    pub fn ascii_prefix(bytes: &[u8]) -> usize {
+        let mut index = 0usize;
+
+        while index + LANES <= bytes.len() {
+            // SAFETY: The chunk lies within the slice, and NEON is enabled.
+            let max = unsafe { vmaxvq_u8(vld1q_u8(bytes.as_ptr().add(index))) };
+
+            if max >= 0x80 {
+                break;
+            }
+
+            index += LANES;
+        }
+
+        index + super::scalar_ascii_prefix(&bytes[index..])
+    }
+}
+
+#[cfg(not(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ),
+    all(target_arch = "aarch64", target_feature = "neon"),
+)))]
+mod imp {
+    // With the `memchr` feature, `mem::find_byte` uses that crate instead.
+    #[cfg(not(feature = "memchr"))]
+    #[inline]
+    pub fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+        super::super::memchr(needle, haystack)
+    }
+
+    #[inline]
+    pub fn ascii_prefix(bytes: &[u8]) -> usize {
+        super::scalar_ascii_prefix(bytes)
+    }
+}
+
+pub use imp::*;
+
+/// Returns the length of the ASCII prefix of `bytes`, a word at a time.
+#[inline]
+fn scalar_ascii_prefix(bytes: &[u8]) -> usize {
+    let mut index = 0usize;
+
+    while index + super::WORD <= bytes.len() {
+        // SAFETY: The word lies within the slice.
+        let word = unsafe { bytes.as_ptr().add(index).cast::<usize>().read_unaligned() };
+
+        if word & super::HI != 0 {
+            break;
+        }
+
+        index += super::WORD;
+    }
+
+    while index < bytes.len() && bytes[index].is_ascii() {
+        index += 1;
+    }
+
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "memchr"))]
+    #[test]
+    fn find_byte_matches_scalar() {
+        let mut haystack = [b'a'; 64];
+
+        for len in 0..=haystack.len() {
+            assert_eq!(find_byte(b'x', &haystack[..len]), None);
+
+            for pos in 0..len {
+                haystack[pos] = b'x';
+                // A second match after the first must not be found instead.
+                if pos + 1 < len {
+                    haystack[len - 1] = b'x';
+                }
+
+                assert_eq!(
+                    find_byte(b'x', &haystack[..len]),
+                    super::super::memchr(b'x', &haystack[..len]),
+                );
+                assert_eq!(find_byte(b'x', &haystack[..len]), Some(pos));
+
+                haystack = [b'a'; 64];
+            }
+        }
+    }
+
+    #[test]
+    fn ascii_prefix_matches_scalar() {
+        let mut bytes = [b'a'; 64];
+
+        for len in 0..=bytes.len() {
+            assert_eq!(ascii_prefix(&bytes[..len]), len);
+
+            for pos in 0..len {
+                bytes[pos] = 0x80;
+
+                assert_eq!(
+                    ascii_prefix(&bytes[..len]),
+                    scalar_ascii_prefix(&bytes[..len]),
+                );
+                assert_eq!(ascii_prefix(&bytes[..len]), pos);
+
+                bytes[pos] = b'a';
+            }
+        }
+    }
+}
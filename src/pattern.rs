@@ -0,0 +1,665 @@
+//! A stable take on the string pattern API.
+//!
+//! This mirrors the unstable [`core::str::pattern`] module closely enough that
+//! the [`DStr`] methods taking a [`Pattern`] behave exactly like their [`str`]
+//! counterparts.
+//!
+//! The following types implement [`Pattern`]:
+//!
+//! - [`char`], matching that character.
+//!
+//! - `[char; N]`, `&[char; N]` and `&[char]`, matching any of the characters.
+//!
+//! - `F: FnMut(char) -> bool`, matching any character `F` returns `true` for.
+//!
+//! - [`&str`](prim@str), `&&str`, `&String` and [`&DStr`](DStr), matching that
+//!   substring. The nul terminator of a [`DStr`] is never part of the needle.
+//!
+//! [`DStr`]: crate::DStr
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::DStr;
+
+/// The result of calling [`Searcher::next`] or [`ReverseSearcher::next_back`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStep {
+    /// A match of the pattern was found at `haystack[a..b]`.
+    Match(usize, usize),
+    /// `haystack[a..b]` was rejected as a possible match of the pattern.
+    ///
+    /// There may be more than one `Reject` between two `Match`es.
+    Reject(usize, usize),
+    /// Every byte of the haystack has been visited.
+    Done,
+}
+
+/// A string pattern.
+///
+/// See the [module-level documentation](self) for the implementors.
+// The method names mirror `core::str::pattern::Pattern`.
+#[allow(clippy::wrong_self_convention)]
+pub trait Pattern: Sized {
+    /// The searcher for this pattern.
+    type Searcher<'a>: Searcher<'a>;
+
+    /// Constructs the associated searcher from `self` and `haystack`.
+    fn into_searcher(self, haystack: &str) -> Self::Searcher<'_>;
+
+    /// Returns whether the pattern matches anywhere in `haystack`.
+    #[inline]
+    fn is_contained_in(self, haystack: &str) -> bool {
+        self.into_searcher(haystack).next_match().is_some()
+    }
+
+    /// Returns whether the pattern matches at the front of `haystack`.
+    #[inline]
+    fn is_prefix_of(self, haystack: &str) -> bool {
+        matches!(self.into_searcher(haystack).next(), SearchStep::Match(0, _))
+    }
+
+    /// Removes the pattern from the front of `haystack`, if it matches.
+    #[inline]
+    fn strip_prefix_of(self, haystack: &str) -> Option<&str> {
+        match self.into_searcher(haystack).next() {
+            // SAFETY: `Searcher` guarantees `end` lies on a char boundary.
+            SearchStep::Match(0, end) => Some(unsafe { haystack.get_unchecked(end..) }),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the pattern matches at the back of `haystack`.
+    #[inline]
+    fn is_suffix_of<'a>(self, haystack: &'a str) -> bool
+    where
+        Self::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        matches!(
+            self.into_searcher(haystack).next_back(),
+            SearchStep::Match(_, end) if end == haystack.len()
+        )
+    }
+
+    /// Removes the pattern from the back of `haystack`, if it matches.
+    #[inline]
+    fn strip_suffix_of<'a>(self, haystack: &'a str) -> Option<&'a str>
+    where
+        Self::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        match self.into_searcher(haystack).next_back() {
+            // SAFETY: `Searcher` guarantees `start` lies on a char boundary.
+            SearchStep::Match(start, end) if end == haystack.len() => {
+                Some(unsafe { haystack.get_unchecked(..start) })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A searcher for a string pattern, walking the haystack from the front.
+///
+/// # Safety
+///
+/// The indices returned in a [`SearchStep`] must lie on char boundaries of
+/// the haystack, and the steps must be non-overlapping and cover the
+/// haystack in increasing order.
+pub unsafe trait Searcher<'a> {
+    /// Returns the haystack that is being searched.
+    fn haystack(&self) -> &'a str;
+
+    /// Performs the next search step starting from the front.
+    fn next(&mut self) -> SearchStep;
+
+    /// Finds the next match, starting from the front.
+    #[inline]
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        loop {
+            match self.next() {
+                SearchStep::Match(start, end) => return Some((start, end)),
+                SearchStep::Done => return None,
+                SearchStep::Reject(..) => {}
+            }
+        }
+    }
+
+    /// Finds the next reject, starting from the front.
+    #[inline]
+    fn next_reject(&mut self) -> Option<(usize, usize)> {
+        loop {
+            match self.next() {
+                SearchStep::Reject(start, end) => return Some((start, end)),
+                SearchStep::Done => return None,
+                SearchStep::Match(..) => {}
+            }
+        }
+    }
+}
+
+/// A searcher for a string pattern, walking the haystack from the back.
+///
+/// # Safety
+///
+/// The same requirements as for [`Searcher`] apply, with the steps covering
+/// the haystack in decreasing order.
+pub unsafe trait ReverseSearcher<'a>: Searcher<'a> {
+    /// Performs the next search step starting from the back.
+    fn next_back(&mut self) -> SearchStep;
+
+    /// Finds the next match, starting from the back.
+    #[inline]
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        loop {
+            match self.next_back() {
+                SearchStep::Match(start, end) => return Some((start, end)),
+                SearchStep::Done => return None,
+                SearchStep::Reject(..) => {}
+            }
+        }
+    }
+
+    /// Finds the next reject, starting from the back.
+    #[inline]
+    fn next_reject_back(&mut self) -> Option<(usize, usize)> {
+        loop {
+            match self.next_back() {
+                SearchStep::Reject(start, end) => return Some((start, end)),
+                SearchStep::Done => return None,
+                SearchStep::Match(..) => {}
+            }
+        }
+    }
+}
+
+/// A marker for a [`ReverseSearcher`] that finds the same matches from
+/// either end, so it can be used for a [`DoubleEndedIterator`].
+pub trait DoubleEndedSearcher<'a>: ReverseSearcher<'a> {}
+
+/// A set of characters that a [`CharSearcher`] matches against.
+pub trait MultiCharEq {
+    /// Returns whether `c` is part of this set.
+    fn matches(&mut self, c: char) -> bool;
+}
+
+impl MultiCharEq for char {
+    #[inline]
+    fn matches(&mut self, c: char) -> bool {
+        *self == c
+    }
+}
+
+impl<F: FnMut(char) -> bool> MultiCharEq for F {
+    #[inline]
+    fn matches(&mut self, c: char) -> bool {
+        (*self)(c)
+    }
+}
+
+impl<const N: usize> MultiCharEq for [char; N] {
+    #[inline]
+    fn matches(&mut self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+impl<const N: usize> MultiCharEq for &[char; N] {
+    #[inline]
+    fn matches(&mut self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+impl MultiCharEq for &[char] {
+    #[inline]
+    fn matches(&mut self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+/// The searcher for every character based [`Pattern`].
+#[derive(Debug, Clone)]
+pub struct CharSearcher<'a, C> {
+    haystack: &'a str,
+    char_eq: C,
+    finger: usize,
+    finger_back: usize,
+}
+
+impl<'a, C: MultiCharEq> CharSearcher<'a, C> {
+    #[inline]
+    fn new(haystack: &'a str, char_eq: C) -> CharSearcher<'a, C> {
+        CharSearcher {
+            haystack,
+            char_eq,
+            finger: 0,
+            finger_back: haystack.len(),
+        }
+    }
+
+    #[inline]
+    fn window(&self) -> &'a str {
+        // SAFETY: Both fingers only ever move by whole chars.
+        unsafe { self.haystack.get_unchecked(self.finger..self.finger_back) }
+    }
+}
+
+unsafe impl<'a, C: MultiCharEq> Searcher<'a> for CharSearcher<'a, C> {
+    #[inline]
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    #[inline]
+    fn next(&mut self) -> SearchStep {
+        match self.window().chars().next() {
+            Some(c) => {
+                let start = self.finger;
+                self.finger += c.len_utf8();
+
+                if self.char_eq.matches(c) {
+                    SearchStep::Match(start, self.finger)
+                } else {
+                    SearchStep::Reject(start, self.finger)
+                }
+            }
+            None => SearchStep::Done,
+        }
+    }
+
+    #[inline]
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let window = self.window();
+        let char_eq = &mut self.char_eq;
+
+        match window.char_indices().find(|&(_, c)| char_eq.matches(c)) {
+            Some((pos, c)) => {
+                let start = self.finger + pos;
+                self.finger = start + c.len_utf8();
+
+                Some((start, self.finger))
+            }
+            None => {
+                self.finger = self.finger_back;
+                None
+            }
+        }
+    }
+}
+
+unsafe impl<'a, C: MultiCharEq> ReverseSearcher<'a> for CharSearcher<'a, C> {
+    #[inline]
+    fn next_back(&mut self) -> SearchStep {
+        match self.window().chars().next_back() {
+            Some(c) => {
+                let end = self.finger_back;
+                self.finger_back -= c.len_utf8();
+
+                if self.char_eq.matches(c) {
+                    SearchStep::Match(self.finger_back, end)
+                } else {
+                    SearchStep::Reject(self.finger_back, end)
+                }
+            }
+            None => SearchStep::Done,
+        }
+    }
+
+    #[inline]
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        let window = self.window();
+        let char_eq = &mut self.char_eq;
+
+        match window.char_indices().rfind(|&(_, c)| char_eq.matches(c)) {
+            Some((pos, c)) => {
+                let end = self.finger + pos + c.len_utf8();
+                self.finger_back = self.finger + pos;
+
+                Some((self.finger_back, end))
+            }
+            None => {
+                self.finger_back = self.finger;
+                None
+            }
+        }
+    }
+}
+
+impl<'a, C: MultiCharEq> DoubleEndedSearcher<'a> for CharSearcher<'a, C> {}
+
+impl Pattern for char {
+    type Searcher<'a> = CharSearcher<'a, char>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> Self::Searcher<'_> {
+        CharSearcher::new(haystack, self)
+    }
+
+    #[inline]
+    fn is_prefix_of(self, haystack: &str) -> bool {
+        haystack.starts_with(self)
+    }
+
+    #[inline]
+    fn is_suffix_of<'a>(self, haystack: &'a str) -> bool
+    where
+        Self::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        haystack.ends_with(self)
+    }
+}
+
+impl<F: FnMut(char) -> bool> Pattern for F {
+    type Searcher<'a> = CharSearcher<'a, F>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> Self::Searcher<'_> {
+        CharSearcher::new(haystack, self)
+    }
+}
+
+impl<const N: usize> Pattern for [char; N] {
+    type Searcher<'a> = CharSearcher<'a, [char; N]>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> Self::Searcher<'_> {
+        CharSearcher::new(haystack, self)
+    }
+}
+
+impl<'b, const N: usize> Pattern for &'b [char; N] {
+    type Searcher<'a> = CharSearcher<'a, &'b [char; N]>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> Self::Searcher<'_> {
+        CharSearcher::new(haystack, self)
+    }
+}
+
+impl<'b> Pattern for &'b [char] {
+    type Searcher<'a> = CharSearcher<'a, &'b [char]>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> Self::Searcher<'_> {
+        CharSearcher::new(haystack, self)
+    }
+}
+
+/// The searcher for every substring based [`Pattern`].
+#[derive(Debug, Clone)]
+pub struct StrSearcher<'a, 'b> {
+    haystack: &'a str,
+    needle: &'b str,
+    finger: usize,
+    finger_back: usize,
+    // Only used for empty needles, which match once at every char boundary
+    // in between rejecting every char.
+    is_match_fw: bool,
+    is_match_bw: bool,
+    finished: bool,
+}
+
+impl<'a, 'b> StrSearcher<'a, 'b> {
+    #[inline]
+    fn new(haystack: &'a str, needle: &'b str) -> StrSearcher<'a, 'b> {
+        StrSearcher {
+            haystack,
+            needle,
+            finger: 0,
+            finger_back: haystack.len(),
+            is_match_fw: true,
+            is_match_bw: true,
+            finished: false,
+        }
+    }
+
+    #[inline]
+    fn window(&self) -> &'a str {
+        // SAFETY: Both fingers only ever move to char boundaries, either by whole
+        //         chars or to the bounds of a match.
+        unsafe { self.haystack.get_unchecked(self.finger..self.finger_back) }
+    }
+}
+
+unsafe impl<'a> Searcher<'a> for StrSearcher<'a, '_> {
+    #[inline]
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        if self.finished {
+            return SearchStep::Done;
+        }
+
+        let window = self.window();
+        let start = self.finger;
+
+        if self.needle.is_empty() {
+            let is_match = self.is_match_fw;
+            self.is_match_fw = !self.is_match_fw;
+
+            return match window.chars().next() {
+                _ if is_match => SearchStep::Match(start, start),
+                Some(c) => {
+                    self.finger += c.len_utf8();
+                    SearchStep::Reject(start, self.finger)
+                }
+                None => {
+                    self.finished = true;
+                    SearchStep::Done
+                }
+            };
+        }
+
+        if window.is_empty() {
+            return SearchStep::Done;
+        }
+
+        if window.starts_with(self.needle) {
+            self.finger += self.needle.len();
+            return SearchStep::Match(start, self.finger);
+        }
+
+        self.finger = match window.find(self.needle) {
+            Some(pos) => start + pos,
+            None => self.finger_back,
+        };
+
+        SearchStep::Reject(start, self.finger)
+    }
+
+    #[inline]
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        if self.needle.is_empty() {
+            loop {
+                match self.next() {
+                    SearchStep::Match(start, end) => return Some((start, end)),
+                    SearchStep::Done => return None,
+                    SearchStep::Reject(..) => {}
+                }
+            }
+        }
+
+        match self.window().find(self.needle) {
+            Some(pos) => {
+                let start = self.finger + pos;
+                self.finger = start + self.needle.len();
+
+                Some((start, self.finger))
+            }
+            None => {
+                self.finger = self.finger_back;
+                None
+            }
+        }
+    }
+}
+
+unsafe impl<'a> ReverseSearcher<'a> for StrSearcher<'a, '_> {
+    fn next_back(&mut self) -> SearchStep {
+        if self.finished {
+            return SearchStep::Done;
+        }
+
+        let window = self.window();
+        let end = self.finger_back;
+
+        if self.needle.is_empty() {
+            let is_match = self.is_match_bw;
+            self.is_match_bw = !self.is_match_bw;
+
+            return match window.chars().next_back() {
+                _ if is_match => SearchStep::Match(end, end),
+                Some(c) => {
+                    self.finger_back -= c.len_utf8();
+                    SearchStep::Reject(self.finger_back, end)
+                }
+                None => {
+                    self.finished = true;
+                    SearchStep::Done
+                }
+            };
+        }
+
+        if window.is_empty() {
+            return SearchStep::Done;
+        }
+
+        if window.ends_with(self.needle) {
+            self.finger_back -= self.needle.len();
+            return SearchStep::Match(self.finger_back, end);
+        }
+
+        self.finger_back = match window.rfind(self.needle) {
+            Some(pos) => self.finger + pos + self.needle.len(),
+            None => self.finger,
+        };
+
+        SearchStep::Reject(self.finger_back, end)
+    }
+
+    #[inline]
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        if self.needle.is_empty() {
+            loop {
+                match self.next_back() {
+                    SearchStep::Match(start, end) => return Some((start, end)),
+                    SearchStep::Done => return None,
+                    SearchStep::Reject(..) => {}
+                }
+            }
+        }
+
+        match self.window().rfind(self.needle) {
+            Some(pos) => {
+                self.finger_back = self.finger + pos;
+
+                Some((self.finger_back, self.finger_back + self.needle.len()))
+            }
+            None => {
+                self.finger_back = self.finger;
+                None
+            }
+        }
+    }
+}
+
+impl<'b> Pattern for &'b str {
+    type Searcher<'a> = StrSearcher<'a, 'b>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> Self::Searcher<'_> {
+        StrSearcher::new(haystack, self)
+    }
+
+    #[inline]
+    fn is_contained_in(self, haystack: &str) -> bool {
+        haystack.contains(self)
+    }
+
+    #[inline]
+    fn is_prefix_of(self, haystack: &str) -> bool {
+        haystack.starts_with(self)
+    }
+
+    #[inline]
+    fn strip_prefix_of(self, haystack: &str) -> Option<&str> {
+        haystack.strip_prefix(self)
+    }
+
+    #[inline]
+    fn is_suffix_of<'a>(self, haystack: &'a str) -> bool
+    where
+        Self::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        haystack.ends_with(self)
+    }
+
+    #[inline]
+    fn strip_suffix_of<'a>(self, haystack: &'a str) -> Option<&'a str>
+    where
+        Self::Searcher<'a>: ReverseSearcher<'a>,
+    {
+        haystack.strip_suffix(self)
+    }
+}
+
+macro_rules! impl_str_pattern {
+    ($(impl<$($lt:lifetime),*> for $ty:ty => |$this:ident| $as_str:expr;)*) => {$(
+        impl<$($lt),*> Pattern for $ty {
+            type Searcher<'a> = StrSearcher<'a, 'b>;
+
+            #[inline]
+            fn into_searcher(self, haystack: &str) -> Self::Searcher<'_> {
+                let $this = self;
+                StrSearcher::new(haystack, $as_str)
+            }
+
+            #[inline]
+            fn is_contained_in(self, haystack: &str) -> bool {
+                let $this = self;
+                $as_str.is_contained_in(haystack)
+            }
+
+            #[inline]
+            fn is_prefix_of(self, haystack: &str) -> bool {
+                let $this = self;
+                $as_str.is_prefix_of(haystack)
+            }
+
+            #[inline]
+            fn strip_prefix_of(self, haystack: &str) -> Option<&str> {
+                let $this = self;
+                $as_str.strip_prefix_of(haystack)
+            }
+
+            #[inline]
+            fn is_suffix_of<'a>(self, haystack: &'a str) -> bool
+            where
+                Self::Searcher<'a>: ReverseSearcher<'a>,
+            {
+                let $this = self;
+                $as_str.is_suffix_of(haystack)
+            }
+
+            #[inline]
+            fn strip_suffix_of<'a>(self, haystack: &'a str) -> Option<&'a str>
+            where
+                Self::Searcher<'a>: ReverseSearcher<'a>,
+            {
+                let $this = self;
+                $as_str.strip_suffix_of(haystack)
+            }
+        }
+    )*};
+}
+
+impl_str_pattern! {
+    impl<'b, 'c> for &'c &'b str => |this| *this;
+    impl<'b> for &'b DStr => |this| this.as_str();
+}
+
+#[cfg(feature = "alloc")]
+impl_str_pattern! {
+    impl<'b> for &'b String => |this| this.as_str();
+}
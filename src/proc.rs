@@ -0,0 +1,92 @@
+//! Module for reading nul-delimited data from `/proc`.
+//!
+//! This is only available on Linux and Android.
+
+use std::{boxed::Box, format, fs, io, path::Path, string::String};
+
+use crate::{DStr, DStrList};
+
+/// Reads the command line arguments of the process `pid` from
+/// `/proc/<pid>/cmdline`.
+///
+/// # Errors
+///
+/// Returns an error if the file could not be read, for example because the
+/// process does not exist, or an error of kind [`io::ErrorKind::InvalidData`]
+/// if an argument is not valid UTF-8.
+#[inline]
+pub fn cmdline(pid: u32) -> io::Result<Box<DStrList>> {
+    read_list(format!("/proc/{pid}/cmdline"))
+}
+
+/// Reads the command line arguments of the current process from
+/// `/proc/self/cmdline`.
+///
+/// # Errors
+///
+/// Returns an error if the file could not be read, or an error of kind
+/// [`io::ErrorKind::InvalidData`] if an argument is not valid UTF-8.
+#[inline]
+pub fn self_cmdline() -> io::Result<Box<DStrList>> {
+    read_list("/proc/self/cmdline")
+}
+
+/// Reads the initial environment of the process `pid` from
+/// `/proc/<pid>/environ`.
+///
+/// This is the environment the process was started with, which does not
+/// reflect later changes. Use [`env_entries`] to split it into keys and
+/// values.
+///
+/// # Errors
+///
+/// Returns an error if the file could not be read, for example because the
+/// process does not exist or belongs to another user, or an error of kind
+/// [`io::ErrorKind::InvalidData`] if an entry is not valid UTF-8.
+#[inline]
+pub fn environ(pid: u32) -> io::Result<Box<DStrList>> {
+    read_list(format!("/proc/{pid}/environ"))
+}
+
+/// Reads the initial environment of the current process from
+/// `/proc/self/environ`.
+///
+/// # Errors
+///
+/// Returns an error if the file could not be read, or an error of kind
+/// [`io::ErrorKind::InvalidData`] if an entry is not valid UTF-8.
+#[inline]
+pub fn self_environ() -> io::Result<Box<DStrList>> {
+    read_list("/proc/self/environ")
+}
+
+/// Returns an iterator over the `KEY=VALUE` entries of an environment, split
+/// at the first `=`.
+///
+/// An entry without a `=` is yielded as a key with an empty value.
+#[inline]
+pub fn env_entries(environ: &DStrList) -> impl Iterator<Item = (&str, &DStr)> + '_ {
+    environ.iter().map(|entry| {
+        entry
+            .split_once('=')
+            .unwrap_or((entry.as_str(), DStr::EMPTY))
+    })
+}
+
+fn read_list<P: AsRef<Path>>(path: P) -> io::Result<Box<DStrList>> {
+    let mut bytes = fs::read(path)?;
+
+    // A process that overwrote its arguments may leave the last one
+    // unterminated.
+    if bytes.last().is_some_and(|&byte| byte != 0) {
+        bytes.push(0);
+    }
+
+    let raw = String::from_utf8(bytes)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.utf8_error()))?
+        .into_boxed_str();
+
+    // SAFETY: `DStrList` is a transparent wrapper around `str`, and the
+    //         string is either empty or ends with a nul.
+    Ok(unsafe { Box::from_raw(Box::into_raw(raw) as *mut DStrList) })
+}
@@ -0,0 +1,234 @@
+//! Module for ELF-style string tables.
+
+use core::{fmt, iter::FusedIterator, ops::Index};
+
+use crate::{
+    dstr::FromBytesError,
+    dstr_list::{self, DStrList},
+    mem, DStr,
+};
+
+mod error;
+pub use error::*;
+
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+pub use builder::*;
+
+/// A string table, such as an ELF `.strtab` or `.dynstr` section, where
+/// strings are referred to by their byte offset.
+///
+/// The table is validated once when it is created, so looking up a string
+/// only scans for its nul terminator. Any offset that lies on a char boundary
+/// within the table refers to a string, including offsets into the middle of
+/// another string, which is how tails are shared.
+///
+/// # Safety
+///
+/// The table upholds the same invariants as a [`DStrList`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct StrTab {
+    raw: str,
+}
+
+impl StrTab {
+    /// An empty [`StrTab`].
+    pub const EMPTY: &'static StrTab = StrTab::from_list(DStrList::EMPTY);
+
+    /// Create a [`StrTab`] from the strings of a [`DStrList`].
+    #[inline]
+    #[must_use]
+    pub const fn from_list(list: &DStrList) -> &StrTab {
+        // SAFETY: A `StrTab` has the same invariants as a `DStrList`.
+        unsafe { &*(list.as_str() as *const str as *const StrTab) }
+    }
+
+    /// Try to create a [`StrTab`] from a string.
+    ///
+    /// Returns an error if the string is not empty and does not end with a
+    /// nul.
+    #[inline]
+    pub const fn try_from_str(string: &str) -> Result<&StrTab, FromBytesError> {
+        match DStrList::try_from_str(string) {
+            Ok(list) => Ok(StrTab::from_list(list)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Try to create a [`StrTab`] from a byte slice.
+    ///
+    /// Returns an error if the bytes are not valid UTF-8, or are not empty and
+    /// do not end with a nul.
+    #[inline]
+    pub const fn try_from_bytes(bytes: &[u8]) -> Result<&StrTab, FromBytesError> {
+        match DStrList::try_from_bytes(bytes) {
+            Ok(list) => Ok(StrTab::from_list(list)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Create a [`StrTab`] from a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes are not valid UTF-8, or are not empty and do not
+    /// end with a nul.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_bytes(bytes: &[u8]) -> &StrTab {
+        StrTab::from_list(DStrList::from_bytes(bytes))
+    }
+
+    /// Returns the underlying string, including every nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the underlying bytes, including every nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.raw.as_bytes()
+    }
+
+    /// Returns the strings of this [`StrTab`] as a [`DStrList`].
+    #[inline]
+    #[must_use]
+    pub const fn as_list(&self) -> &DStrList {
+        // SAFETY: A `StrTab` has the same invariants as a `DStrList`.
+        unsafe { DStrList::from_str_unchecked(&self.raw) }
+    }
+
+    /// Returns whether this [`StrTab`] contains no strings.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Returns the string at byte offset `offset`.
+    ///
+    /// Returns an error if `offset` is out of bounds, or does not lie on a
+    /// char boundary.
+    #[inline]
+    pub const fn get(&self, offset: usize) -> Result<&DStr, StrTabError> {
+        if offset >= self.raw.len() {
+            return Err(StrTabError::OutOfBounds(offset));
+        }
+
+        if !self.raw.is_char_boundary(offset) {
+            return Err(StrTabError::NotCharBoundary(offset));
+        }
+
+        // SAFETY: `offset` is in bounds.
+        let rest = unsafe {
+            core::slice::from_raw_parts(self.raw.as_ptr().add(offset), self.raw.len() - offset)
+        };
+
+        // The table always ends with a nul, so there is one after `offset`.
+        let len = match mem::memchr(0, rest) {
+            Some(nul) => nul + 1,
+            None => rest.len(),
+        };
+
+        // SAFETY: `offset` lies on a char boundary, and the slice ends at the
+        //         first nul after it.
+        Ok(unsafe {
+            DStr::from_bytes_with_nul_unchecked(core::slice::from_raw_parts(rest.as_ptr(), len))
+        })
+    }
+
+    /// Returns an iterator over the strings of this [`StrTab`] and their
+    /// offsets.
+    ///
+    /// Shared tails are not visited on their own.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.as_list().iter(),
+            offset: 0,
+        }
+    }
+}
+
+impl fmt::Debug for StrTab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for StrTab {
+    type Output = DStr;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, offset: usize) -> &Self::Output {
+        match self.get(offset) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a StrTab {
+    type Item = (usize, &'a DStr);
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> From<&'a DStrList> for &'a StrTab {
+    #[inline]
+    fn from(value: &'a DStrList) -> Self {
+        StrTab::from_list(value)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for &'a StrTab {
+    type Error = FromBytesError;
+
+    #[inline]
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        StrTab::try_from_bytes(value)
+    }
+}
+
+/// An iterator over the strings in a [`StrTab`] and their offsets.
+///
+/// This is created by [`StrTab::iter`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a> {
+    inner: dstr_list::Iter<'a>,
+    offset: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (usize, &'a DStr);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let string = self.inner.next()?;
+        let offset = self.offset;
+
+        self.offset += string.len_with_nul().get();
+
+        Some((offset, string))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl FusedIterator for Iter<'_> {}
@@ -0,0 +1,181 @@
+use core::{fmt, ops::Deref};
+
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{
+    dstr::NulError,
+    dstr_interner::{DStrInterner, Symbol},
+    DStr, DStrList, StrTab,
+};
+
+/// A builder for a [`StrTab`], which deduplicates identical strings.
+///
+/// Like an ELF string table, the table always starts with a nul, so offset
+/// zero is the empty string. Offsets are assigned by [`StrTabBuilder::finish`],
+/// which can also share the storage of strings that are the tail of another,
+/// such as `"bar"` within `"foobar"`.
+#[derive(Debug, Default)]
+pub struct StrTabBuilder {
+    strings: DStrInterner,
+    tail_merge: bool,
+}
+
+impl StrTabBuilder {
+    /// Creates a new, empty [`StrTabBuilder`], which does not merge tails.
+    #[inline]
+    #[must_use]
+    pub fn new() -> StrTabBuilder {
+        StrTabBuilder {
+            strings: DStrInterner::new(),
+            tail_merge: false,
+        }
+    }
+
+    /// Sets whether strings that are the tail of another share its storage.
+    ///
+    /// Without this, strings are laid out in the order they were first
+    /// added.
+    #[inline]
+    pub fn set_tail_merge(&mut self, tail_merge: bool) {
+        self.tail_merge = tail_merge;
+    }
+
+    /// Adds `string` to the table, returning the symbol to look up its offset
+    /// with once finished.
+    #[inline]
+    #[track_caller]
+    pub fn add(&mut self, string: &DStr) -> Symbol {
+        self.strings.get_or_intern(string)
+    }
+
+    /// Adds `string` to the table, returning the symbol to look up its offset
+    /// with once finished.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul.
+    #[inline]
+    #[track_caller]
+    pub fn add_str(&mut self, string: &str) -> Symbol {
+        self.strings.get_or_intern_str(string)
+    }
+
+    /// Try to add `string` to the table, returning the symbol to look up its
+    /// offset with once finished.
+    #[inline]
+    #[track_caller]
+    pub fn try_add_str(&mut self, string: &str) -> Result<Symbol, NulError> {
+        self.strings.try_get_or_intern_str(string)
+    }
+
+    /// Returns the amount of distinct strings added.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns whether no strings were added.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Lays out the table, assigning an offset to every string.
+    #[must_use]
+    pub fn finish(&self) -> StrTabBuf {
+        let mut data = String::from("\0");
+        let mut offsets = vec![0; self.strings.len()];
+
+        if self.tail_merge {
+            let mut strings: Vec<_> = self
+                .strings
+                .iter()
+                .filter(|(_, string)| !string.is_empty())
+                .collect();
+
+            // Sorting by the reversed strings in descending order puts every
+            // string directly after one it is a tail of, if there is any.
+            strings.sort_unstable_by(|(_, a), (_, b)| {
+                b.as_bytes().iter().rev().cmp(a.as_bytes().iter().rev())
+            });
+
+            let mut prev: Option<(usize, &DStr)> = None;
+
+            for (symbol, string) in strings {
+                let offset = match prev {
+                    Some((offset, prev)) if prev.as_bytes().ends_with(string.as_bytes()) => {
+                        offset + prev.len() - string.len()
+                    }
+                    _ => {
+                        let offset = data.len();
+                        data.push_str(string.as_str_with_nul());
+                        offset
+                    }
+                };
+
+                offsets[symbol.index()] = offset;
+                prev = Some((offset, string));
+            }
+        } else {
+            for (symbol, string) in &self.strings {
+                if !string.is_empty() {
+                    offsets[symbol.index()] = data.len();
+                    data.push_str(string.as_str_with_nul());
+                }
+            }
+        }
+
+        StrTabBuf { data, offsets }
+    }
+}
+
+/// An owned [`StrTab`] built by a [`StrTabBuilder`], along with the offset of
+/// every string added to it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StrTabBuf {
+    data: String,
+    /// The offset of every string, indexed by symbol.
+    offsets: Vec<usize>,
+}
+
+impl StrTabBuf {
+    /// Returns the offset of the string of `symbol`, or `None` if it is not
+    /// from the builder of this table.
+    #[inline]
+    #[must_use]
+    pub fn offset(&self, symbol: Symbol) -> Option<usize> {
+        self.offsets.get(symbol.index()).copied()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_str_tab(&self) -> &StrTab {
+        // SAFETY: The table always ends with a nul.
+        unsafe { StrTab::from_list(DStrList::from_str_unchecked(&self.data)) }
+    }
+
+    /// Consumes this [`StrTabBuf`], returning the bytes of the table.
+    #[inline]
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data.into_bytes()
+    }
+}
+
+impl fmt::Debug for StrTabBuf {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str_tab(), f)
+    }
+}
+
+impl Deref for StrTabBuf {
+    type Target = StrTab;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str_tab()
+    }
+}
@@ -0,0 +1,68 @@
+use core::fmt;
+
+use crate::dstr::{msg, DStrError, ErrorKind};
+
+/// An error indicating that an offset does not point to a string in a
+/// [`StrTab`].
+///
+/// [`StrTab`]: crate::StrTab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrTabError {
+    OutOfBounds(usize),
+    NotCharBoundary(usize),
+}
+
+impl StrTabError {
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            StrTabError::OutOfBounds(_) => msg!(OutOfBounds),
+            StrTabError::NotCharBoundary(_) => msg!(NotCharBoundary),
+        }
+    }
+
+    /// Returns the offset that was looked up.
+    #[inline]
+    #[must_use]
+    pub const fn offset(self) -> usize {
+        match self {
+            StrTabError::OutOfBounds(offset) | StrTabError::NotCharBoundary(offset) => offset,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for StrTabError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        core::write!(f, " at pos {}", self.offset())
+    }
+}
+
+impl core::error::Error for StrTabError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<StrTabError> for DStrError {
+    #[inline]
+    fn from(value: StrTabError) -> Self {
+        match value {
+            StrTabError::OutOfBounds(offset) => {
+                DStrError::new(ErrorKind::OutOfBounds, Some(offset))
+            }
+            StrTabError::NotCharBoundary(offset) => {
+                DStrError::new(ErrorKind::NotCharBoundary, Some(offset))
+            }
+        }
+    }
+}
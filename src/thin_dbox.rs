@@ -0,0 +1,196 @@
+//! Module for thin owned nul-terminated UTF-8 strings.
+
+use core::{
+    cmp::Ordering,
+    ffi::c_char,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    ptr::{self, NonNull},
+};
+
+use alloc::boxed::Box;
+
+use crate::{DStr, DString, ThinDStr};
+
+/// An owned nul-terminated UTF-8 string on the heap, represented as a single
+/// pointer.
+///
+/// The allocation holds exactly the string and its nul terminator. Like a
+/// [`ThinDStr`], the length is not stored, so it is computed every time it is
+/// needed, including when this is dropped. `Option<ThinDBox>` is also a single
+/// pointer.
+#[repr(transparent)]
+pub struct ThinDBox {
+    ptr: NonNull<c_char>,
+}
+
+// SAFETY: A `ThinDBox` behaves the same as a `Box<DStr>`.
+unsafe impl Send for ThinDBox {}
+
+// SAFETY: A `ThinDBox` behaves the same as a `Box<DStr>`.
+unsafe impl Sync for ThinDBox {}
+
+impl ThinDBox {
+    /// Creates a new [`ThinDBox`] by copying `string`.
+    #[inline]
+    #[must_use]
+    pub fn new(string: &DStr) -> ThinDBox {
+        let bytes: Box<[u8]> = Box::from(string.as_bytes_with_nul());
+
+        // SAFETY: The buffer is copied from a valid `DStr`.
+        unsafe { ThinDBox::from_boxed_bytes_unchecked(bytes) }
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` is nul-terminated UTF-8 with no
+    /// interior nuls.
+    #[inline]
+    unsafe fn from_boxed_bytes_unchecked(bytes: Box<[u8]>) -> ThinDBox {
+        let ptr = Box::into_raw(bytes).cast::<c_char>();
+
+        ThinDBox {
+            // SAFETY: Boxes are never null.
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+        }
+    }
+
+    /// Create a [`ThinDBox`] from a pointer returned by [`ThinDBox::into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` was returned by [`ThinDBox::into_raw`],
+    /// and that the string was not changed since.
+    #[inline]
+    #[must_use]
+    pub unsafe fn from_raw(ptr: *mut c_char) -> ThinDBox {
+        debug_assert!(!ptr.is_null(), "a `ThinDBox` must not be null");
+
+        ThinDBox {
+            // SAFETY: The caller ensures `ptr` came from `into_raw`.
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+        }
+    }
+
+    /// Consumes this [`ThinDBox`], returning a raw pointer to the string.
+    ///
+    /// The memory can be freed again with [`ThinDBox::from_raw`].
+    #[inline]
+    #[must_use = "losing the pointer will leak memory"]
+    pub fn into_raw(self) -> *mut c_char {
+        let ptr = self.ptr.as_ptr();
+        core::mem::forget(self);
+
+        ptr
+    }
+
+    /// Returns a [`ThinDStr`] pointing to this string.
+    #[inline]
+    #[must_use]
+    pub const fn as_thin(&self) -> ThinDStr<'_> {
+        // SAFETY: `ptr` points to valid nul-terminated UTF-8, which lives
+        //         as long as `self`.
+        unsafe { ThinDStr::from_ptr(self.ptr.as_ptr()) }
+    }
+
+    /// Converts this [`ThinDBox`] to a [`DStr`].
+    ///
+    /// This scans the string for the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_dstr(&self) -> &DStr {
+        self.as_thin().as_dstr()
+    }
+}
+
+impl Drop for ThinDBox {
+    #[inline]
+    fn drop(&mut self) {
+        let len = self.as_thin().len() + 1;
+        let bytes = ptr::slice_from_raw_parts_mut(self.ptr.as_ptr().cast::<u8>(), len);
+
+        // SAFETY: The allocation was created from a boxed slice of `len` bytes,
+        //         since the string contains no interior nuls.
+        drop(unsafe { Box::from_raw(bytes) });
+    }
+}
+
+impl Clone for ThinDBox {
+    #[inline]
+    fn clone(&self) -> Self {
+        ThinDBox::new(self.as_dstr())
+    }
+}
+
+impl PartialEq for ThinDBox {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_dstr() == other.as_dstr()
+    }
+}
+
+impl Eq for ThinDBox {}
+
+impl PartialOrd for ThinDBox {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ThinDBox {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_dstr().cmp(other.as_dstr())
+    }
+}
+
+impl Hash for ThinDBox {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_dstr().hash(state)
+    }
+}
+
+impl fmt::Debug for ThinDBox {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dstr(), f)
+    }
+}
+
+/// Dereferences to a [`DStr`], which scans the string for the nul terminator.
+impl Deref for ThinDBox {
+    type Target = DStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_dstr()
+    }
+}
+
+impl From<&DStr> for ThinDBox {
+    #[inline]
+    fn from(value: &DStr) -> Self {
+        ThinDBox::new(value)
+    }
+}
+
+impl From<DString> for ThinDBox {
+    /// This reuses the allocation of the [`DString`], only shrinking it.
+    #[inline]
+    fn from(value: DString) -> Self {
+        let bytes = value.into_bytes_with_nul().into_boxed_slice();
+
+        // SAFETY: The buffer of a `DString` is always valid.
+        unsafe { ThinDBox::from_boxed_bytes_unchecked(bytes) }
+    }
+}
+
+impl From<ThinDBox> for DString {
+    #[inline]
+    fn from(value: ThinDBox) -> Self {
+        DString::from(value.as_dstr())
+    }
+}
@@ -0,0 +1,193 @@
+//! Module for thin borrowed nul-terminated UTF-8 strings.
+
+use core::{
+    cmp::Ordering,
+    ffi::{c_char, CStr},
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ptr::NonNull,
+    slice::from_raw_parts,
+    str::Utf8Error,
+};
+
+use crate::{mem, DStr};
+
+/// A borrowed nul-terminated UTF-8 string, represented as a single pointer.
+///
+/// This is layout compatible with a non-null `const char *`, and since the
+/// pointer is never null, `Option<ThinDStr>` is too. Unlike a [`DStr`], the
+/// length is not stored, so it is computed every time it is needed.
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct ThinDStr<'a> {
+    ptr: NonNull<c_char>,
+    marker: PhantomData<&'a DStr>,
+}
+
+// SAFETY: A `ThinDStr` behaves the same as a `&DStr`.
+unsafe impl Send for ThinDStr<'_> {}
+
+// SAFETY: A `ThinDStr` behaves the same as a `&DStr`.
+unsafe impl Sync for ThinDStr<'_> {}
+
+impl<'a> ThinDStr<'a> {
+    /// Creates a [`ThinDStr`] pointing to the same string as `string`.
+    #[inline]
+    #[must_use]
+    pub const fn from_dstr(string: &'a DStr) -> ThinDStr<'a> {
+        // SAFETY: References are never null.
+        let ptr = unsafe { NonNull::new_unchecked(string.as_c_ptr().cast_mut()) };
+
+        ThinDStr {
+            ptr,
+            marker: PhantomData,
+        }
+    }
+
+    /// Try to create a [`ThinDStr`] pointing to the same string as `string`.
+    ///
+    /// Returns an error if `string` is not valid UTF-8.
+    #[inline]
+    pub const fn try_from_cstr(string: &'a CStr) -> Result<ThinDStr<'a>, Utf8Error> {
+        match core::str::from_utf8(string.to_bytes()) {
+            // SAFETY: We just checked that `string` is valid UTF-8.
+            Ok(_) => Ok(unsafe { ThinDStr::from_ptr(string.as_ptr()) }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Create a [`ThinDStr`] from a raw pointer without doing any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the following:
+    ///
+    /// - `ptr` is not null, and points to a nul-terminated string.
+    ///
+    /// - The string is valid UTF-8, and the nul is within [`isize::MAX`]
+    ///   bytes from `ptr`.
+    ///
+    /// - The string is not mutated or deallocated for the lifetime `'a`.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn from_ptr(ptr: *const c_char) -> ThinDStr<'a> {
+        debug_assert!(!ptr.is_null(), "a `ThinDStr` must not be null");
+
+        ThinDStr {
+            // SAFETY: The caller ensures `ptr` is not null.
+            ptr: unsafe { NonNull::new_unchecked(ptr.cast_mut()) },
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the length of this [`ThinDStr`] in bytes, excluding the nul
+    /// terminator.
+    ///
+    /// This scans the string for the nul terminator every time.
+    #[inline]
+    #[must_use]
+    pub const fn len(self) -> usize {
+        // SAFETY: `ptr` always points to a nul-terminated string.
+        unsafe { mem::find_nul(self.ptr.as_ptr()) }
+    }
+
+    /// Returns whether this [`ThinDStr`] is empty.
+    ///
+    /// Unlike [`ThinDStr::len`], this only looks at the first byte.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        // SAFETY: `ptr` always points to at least the nul terminator.
+        unsafe { *self.ptr.as_ptr() == 0 }
+    }
+
+    /// Returns a raw pointer to the start of this [`ThinDStr`].
+    #[inline]
+    #[must_use]
+    pub const fn as_ptr(self) -> *const u8 {
+        self.ptr.as_ptr().cast_const().cast()
+    }
+
+    /// Returns a raw C pointer to the start of this [`ThinDStr`].
+    #[inline]
+    #[must_use]
+    pub const fn as_c_ptr(self) -> *const c_char {
+        self.ptr.as_ptr().cast_const()
+    }
+
+    /// Converts this [`ThinDStr`] to a [`DStr`].
+    ///
+    /// This scans the string for the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_dstr(self) -> &'a DStr {
+        let len = self.len();
+
+        // SAFETY: The string is valid for `'a`, and the nul is at `len`.
+        let bytes = unsafe { from_raw_parts(self.as_ptr(), len + 1) };
+
+        // SAFETY: The string is valid UTF-8 with no interior nuls.
+        unsafe { DStr::from_bytes_with_nul_unchecked(bytes) }
+    }
+}
+
+impl PartialEq for ThinDStr<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_dstr() == other.as_dstr()
+    }
+}
+
+impl Eq for ThinDStr<'_> {}
+
+impl PartialOrd for ThinDStr<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ThinDStr<'_> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_dstr().cmp(other.as_dstr())
+    }
+}
+
+impl Hash for ThinDStr<'_> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_dstr().hash(state)
+    }
+}
+
+impl fmt::Debug for ThinDStr<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dstr(), f)
+    }
+}
+
+impl<'a> From<&'a DStr> for ThinDStr<'a> {
+    #[inline]
+    fn from(value: &'a DStr) -> Self {
+        ThinDStr::from_dstr(value)
+    }
+}
+
+impl<'a> From<ThinDStr<'a>> for &'a DStr {
+    #[inline]
+    fn from(value: ThinDStr<'a>) -> Self {
+        value.as_dstr()
+    }
+}
+
+impl<'a> TryFrom<&'a CStr> for ThinDStr<'a> {
+    type Error = Utf8Error;
+
+    #[inline]
+    fn try_from(value: &'a CStr) -> Result<Self, Self::Error> {
+        ThinDStr::try_from_cstr(value)
+    }
+}
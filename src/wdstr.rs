@@ -0,0 +1,253 @@
+//! Module for borrowed nul-terminated UTF-16 strings.
+
+use core::{
+    char::{decode_utf16, DecodeUtf16},
+    fmt::{self, Write},
+    iter::{Copied, FusedIterator},
+    num::NonZeroUsize,
+    slice::{self, from_raw_parts},
+};
+
+mod error;
+pub use error::*;
+
+use crate::mem;
+#[cfg(feature = "alloc")]
+use crate::DString;
+
+/// A nul-terminated UTF-16 string, such as a Windows `LPCWSTR`.
+///
+/// This is the UTF-16 counterpart of a [`DStr`], and has the same
+/// representation, except that it consists of [`u16`]s rather than bytes.
+///
+/// # Safety
+///
+/// - The internal representation must always be valid UTF-16, so there are
+///   no unpaired surrogates.
+///
+/// - There must be no internal nuls, and the last [`u16`] is always the nul
+///   terminator.
+///
+/// [`DStr`]: crate::DStr
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct WDStr {
+    raw: [u16],
+}
+
+impl WDStr {
+    /// An empty [`WDStr`], containing only the nul terminator.
+    pub const EMPTY: &'static WDStr = WDStr::from_slice_with_nul(&[0]);
+
+    /// Returns the length of this [`WDStr`] in [`u16`]s, including the
+    /// nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn len_with_nul(&self) -> NonZeroUsize {
+        debug_assert!(
+            !self.raw.is_empty(),
+            "a nul-terminated string must have at least a nul"
+        );
+
+        // SAFETY: A nul-terminated string always has at least the nul.
+        unsafe { NonZeroUsize::new_unchecked(self.raw.len()) }
+    }
+
+    /// Returns the length of this [`WDStr`] in [`u16`]s, excluding the
+    /// nul terminator.
+    ///
+    /// This is equivalent to `wcslen(self.as_ptr())` on Windows.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len_with_nul().get() - 1
+    }
+
+    /// Returns whether this [`WDStr`] is empty.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        // SAFETY: A nul-terminated string always contains at least the nul.
+        unsafe { *self.raw.as_ptr() == 0 }
+    }
+
+    /// Converts this [`WDStr`] to a `&[u16]` containing the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_slice_with_nul(&self) -> &[u16] {
+        &self.raw
+    }
+
+    /// Converts this [`WDStr`] to a `&[u16]` excluding the nul terminator.
+    #[inline]
+    #[must_use]
+    pub const fn as_slice(&self) -> &[u16] {
+        // SAFETY: The first `len` units are always in bounds.
+        unsafe { from_raw_parts(self.raw.as_ptr(), self.len()) }
+    }
+
+    /// Returns a raw pointer to the start of this [`WDStr`].
+    #[inline]
+    #[must_use]
+    pub const fn as_ptr(&self) -> *const u16 {
+        self.raw.as_ptr()
+    }
+
+    /// Create a [`WDStr`] from a nul-terminated slice without doing any
+    /// checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the input is a nul-terminated UTF-16
+    /// slice with no interior nuls.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn from_slice_with_nul_unchecked(slice: &[u16]) -> &WDStr {
+        // SAFETY: `WDStr` is a transparent wrapper around `[u16]`, and the
+        //         caller ensures the slice is a valid `WDStr`.
+        unsafe { &*(slice as *const [u16] as *const WDStr) }
+    }
+
+    /// Try to create a [`WDStr`] from a nul-terminated UTF-16 slice.
+    ///
+    /// Returns an error if the slice is not nul-terminated, contains interior
+    /// nuls, or is not valid UTF-16, in which case the position is that of
+    /// the offending [`u16`].
+    #[inline]
+    pub const fn try_from_slice_with_nul(slice: &[u16]) -> Result<&WDStr, FromWideError> {
+        let len = slice.len();
+        let mut idx = 0;
+
+        while idx < len {
+            match slice[idx] {
+                0 if idx + 1 == len => {
+                    // SAFETY: The only nul is the terminator, and every
+                    //         surrogate before it was paired.
+                    return Ok(unsafe { WDStr::from_slice_with_nul_unchecked(slice) });
+                }
+                0 => return Err(FromWideError::InteriorNul(idx)),
+                0xD800..=0xDBFF if idx + 1 < len && matches!(slice[idx + 1], 0xDC00..=0xDFFF) => {
+                    idx += 2;
+                }
+                0xD800..=0xDFFF => return Err(FromWideError::InvalidUtf16(idx)),
+                _ => idx += 1,
+            }
+        }
+
+        Err(FromWideError::NotNulTerminated)
+    }
+
+    /// Create a [`WDStr`] from a nul-terminated UTF-16 slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is not nul-terminated, contains interior nuls, or
+    /// is not valid UTF-16.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub const fn from_slice_with_nul(slice: &[u16]) -> &WDStr {
+        match WDStr::try_from_slice_with_nul(slice) {
+            Ok(string) => string,
+            Err(err) => err._panic(),
+        }
+    }
+
+    /// Try to create a [`WDStr`] from a raw pointer, computing the length in
+    /// the same way as `wcslen`.
+    ///
+    /// Returns an error if the string is not valid UTF-16.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the following:
+    ///
+    /// - `ptr` is not null, and points to a nul-terminated string.
+    ///
+    /// - The nul is within [`isize::MAX`] bytes from `ptr`.
+    ///
+    /// - The string is not mutated or deallocated for the lifetime `'a`.
+    #[inline]
+    pub const unsafe fn from_ptr<'a>(ptr: *const u16) -> Result<&'a WDStr, FromWideError> {
+        // SAFETY: The caller ensures `ptr` points to a nul-terminated string.
+        let len = unsafe { mem::wcslen(ptr) };
+
+        // SAFETY: The string is `len + 1` units long, including the nul.
+        WDStr::try_from_slice_with_nul(unsafe { from_raw_parts(ptr, len + 1) })
+    }
+
+    /// Returns an iterator over the [`char`]s of this [`WDStr`], excluding the
+    /// nul terminator.
+    #[inline]
+    pub fn chars(&self) -> Chars<'_> {
+        Chars {
+            inner: decode_utf16(self.as_slice().iter().copied()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl WDStr {
+    /// Converts this [`WDStr`] to a UTF-8 [`DString`].
+    ///
+    /// Since a [`WDStr`] is always valid UTF-16 with no interior nuls, this
+    /// can not fail.
+    #[must_use]
+    pub fn to_dstring(&self) -> DString {
+        let mut string = DString::with_capacity(self.len());
+        string.extend(self.chars());
+        string
+    }
+}
+
+impl fmt::Debug for WDStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+
+        for ch in self.chars() {
+            for esc in ch.escape_debug() {
+                f.write_char(esc)?;
+            }
+        }
+
+        f.write_char('"')
+    }
+}
+
+impl fmt::Display for WDStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ch in self.chars() {
+            f.write_char(ch)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An iterator over the [`char`]s of a [`WDStr`].
+///
+/// This is created by [`WDStr::chars`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Chars<'a> {
+    inner: DecodeUtf16<Copied<slice::Iter<'a, u16>>>,
+}
+
+impl Iterator for Chars<'_> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        // A `WDStr` is always valid UTF-16, so this never yields a replacement.
+        self.inner
+            .next()
+            .map(|ch| ch.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl FusedIterator for Chars<'_> {}
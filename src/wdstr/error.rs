@@ -0,0 +1,61 @@
+use core::fmt;
+
+use crate::dstr::{msg, DStrError, ErrorKind};
+
+/// An error indicating that a slice of [`u16`]s is not a valid [`WDStr`].
+///
+/// [`WDStr`]: crate::WDStr
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromWideError {
+    NotNulTerminated,
+    InteriorNul(usize),
+    InvalidUtf16(usize),
+}
+
+impl FromWideError {
+    #[inline]
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            FromWideError::NotNulTerminated => msg!(NotNulTerminated),
+            FromWideError::InteriorNul(_) => msg!(InteriorNul),
+            FromWideError::InvalidUtf16(_) => msg!(InvalidUtf16),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn _panic<T>(self) -> T {
+        panic!("{}", self.message())
+    }
+}
+
+impl fmt::Display for FromWideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+
+        if let &Self::InteriorNul(pos) | &Self::InvalidUtf16(pos) = self {
+            core::write!(f, " at pos {pos}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for FromWideError {
+    #[allow(deprecated)]
+    #[inline]
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl From<FromWideError> for DStrError {
+    #[inline]
+    fn from(value: FromWideError) -> Self {
+        match value {
+            FromWideError::NotNulTerminated => DStrError::new(ErrorKind::NotNulTerminated, None),
+            FromWideError::InteriorNul(pos) => DStrError::new(ErrorKind::InteriorNul, Some(pos)),
+            FromWideError::InvalidUtf16(pos) => DStrError::new(ErrorKind::InvalidUtf16, Some(pos)),
+        }
+    }
+}
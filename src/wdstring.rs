@@ -0,0 +1,325 @@
+//! Module for owned nul-terminated UTF-16 strings.
+
+use core::{
+    char::decode_utf16,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use alloc::{vec, vec::Vec};
+
+use crate::{dstr::NulError, wdstr::FromWideError, DStr, WDStr};
+
+/// An owned nul-terminated UTF-16 string, such as a Windows `LPWSTR`.
+///
+/// This is the UTF-16 counterpart of a [`DString`].
+///
+/// # Safety
+///
+/// The buffer must always uphold the invariants of a [`WDStr`].
+///
+/// [`DString`]: crate::DString
+pub struct WDString {
+    inner: Vec<u16>,
+}
+
+impl WDString {
+    /// Creates a new, empty [`WDString`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> WDString {
+        WDString { inner: vec![0] }
+    }
+
+    /// Creates a new, empty [`WDString`] with room for at least `capacity`
+    /// [`u16`]s, excluding the nul terminator.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> WDString {
+        let mut inner = Vec::with_capacity(capacity.saturating_add(1));
+        inner.push(0);
+
+        WDString { inner }
+    }
+
+    /// Try to create a [`WDString`] by encoding `string` as UTF-16.
+    ///
+    /// The error contains the byte position of the nul within `string`.
+    #[inline]
+    pub fn try_from_str(string: &str) -> Result<WDString, NulError> {
+        let mut this = WDString::with_capacity(string.len());
+        this.try_push_str(string)?;
+
+        Ok(this)
+    }
+
+    /// Create a [`WDString`] from a nul-terminated UTF-16 buffer without doing
+    /// any checks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `vec` is a nul-terminated UTF-16 buffer
+    /// with no interior nuls.
+    #[inline]
+    #[must_use]
+    pub unsafe fn from_vec_with_nul_unchecked(vec: Vec<u16>) -> WDString {
+        WDString { inner: vec }
+    }
+
+    /// Try to create a [`WDString`] from a nul-terminated UTF-16 buffer.
+    #[inline]
+    pub fn try_from_vec_with_nul(vec: Vec<u16>) -> Result<WDString, FromWideError> {
+        WDStr::try_from_slice_with_nul(&vec)?;
+
+        // SAFETY: We just checked that `vec` is a valid `WDStr`.
+        Ok(unsafe { WDString::from_vec_with_nul_unchecked(vec) })
+    }
+
+    /// Returns the capacity of this [`WDString`] in [`u16`]s, excluding the
+    /// nul terminator.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity() - 1
+    }
+
+    /// Reserves room for at least `additional` more [`u16`]s.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional);
+    }
+
+    /// Appends `ch` to the end of this [`WDString`], before the nul terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, ch: char) {
+        if let Err(err) = self.try_push(ch) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `ch` to the end of this [`WDString`], before the nul
+    /// terminator.
+    #[inline]
+    pub fn try_push(&mut self, ch: char) -> Result<(), NulError> {
+        if ch == '\0' {
+            return Err(NulError::new(0));
+        }
+
+        // SAFETY: We just checked that `ch` is not a nul.
+        unsafe { self.push_units_unchecked(ch.encode_utf16(&mut [0; 2])) };
+
+        Ok(())
+    }
+
+    /// Appends `string` to the end of this [`WDString`], before the nul
+    /// terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a nul.
+    #[inline]
+    #[track_caller]
+    pub fn push_str(&mut self, string: &str) {
+        if let Err(err) = self.try_push_str(string) {
+            err._panic()
+        }
+    }
+
+    /// Try to append `string` to the end of this [`WDString`], before the nul
+    /// terminator.
+    ///
+    /// The error contains the byte position of the nul within `string`.
+    #[inline]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), NulError> {
+        NulError::check(string.as_bytes())?;
+
+        // SAFETY: We just checked that `string` contains no nuls.
+        unsafe { self.push_str_unchecked(string) };
+
+        Ok(())
+    }
+
+    /// Appends `string` to the end of this [`WDString`], before the nul
+    /// terminator.
+    ///
+    /// Since a [`DStr`] is already known to be valid, this doesn't do any
+    /// checks.
+    #[inline]
+    pub fn push_dstr(&mut self, string: &DStr) {
+        // SAFETY: A `DStr` contains no interior nuls.
+        unsafe { self.push_str_unchecked(string.as_str()) };
+    }
+
+    /// Appends `string` to the end of this [`WDString`], before the nul
+    /// terminator.
+    #[inline]
+    pub fn push_wdstr(&mut self, string: &WDStr) {
+        // SAFETY: A `WDStr` is valid UTF-16 with no interior nuls.
+        unsafe { self.push_units_unchecked(string.as_slice()) };
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that `string` contains no nuls.
+    #[inline]
+    unsafe fn push_str_unchecked(&mut self, string: &str) {
+        self.inner.pop();
+        self.inner.extend(string.encode_utf16());
+        self.inner.push(0);
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that `units` is valid UTF-16 with no nuls.
+    #[inline]
+    unsafe fn push_units_unchecked(&mut self, units: &[u16]) {
+        self.inner.pop();
+        self.inner.extend_from_slice(units);
+        self.inner.push(0);
+    }
+
+    /// Removes the last [`char`] of this [`WDString`] and returns it, or `None`
+    /// if it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<char> {
+        let units = self.as_slice();
+        let last = *units.last()?;
+
+        // A low surrogate is always the second half of a pair.
+        let width = if (0xDC00..=0xDFFF).contains(&last) {
+            2
+        } else {
+            1
+        };
+        let len = units.len() - width;
+
+        let ch = decode_utf16(units[len..].iter().copied())
+            .next()?
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+
+        self.inner.truncate(len);
+        self.inner.push(0);
+
+        Some(ch)
+    }
+
+    /// Removes all contents of this [`WDString`], keeping the capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.inner.push(0);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_wdstr(&self) -> &WDStr {
+        // SAFETY: The buffer always upholds the invariants of a `WDStr`.
+        unsafe { WDStr::from_slice_with_nul_unchecked(&self.inner) }
+    }
+
+    /// Consumes this [`WDString`], returning the buffer including the nul
+    /// terminator.
+    #[inline]
+    #[must_use]
+    pub fn into_vec_with_nul(self) -> Vec<u16> {
+        self.inner
+    }
+}
+
+impl Default for WDString {
+    #[inline]
+    fn default() -> Self {
+        WDString::new()
+    }
+}
+
+impl Clone for WDString {
+    #[inline]
+    fn clone(&self) -> Self {
+        WDString {
+            inner: self.inner.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+    }
+}
+
+impl PartialEq for WDString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_wdstr() == other.as_wdstr()
+    }
+}
+
+impl Eq for WDString {}
+
+impl PartialOrd for WDString {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WDString {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_wdstr().cmp(other.as_wdstr())
+    }
+}
+
+impl Hash for WDString {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_wdstr().hash(state)
+    }
+}
+
+impl fmt::Debug for WDString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_wdstr(), f)
+    }
+}
+
+impl fmt::Display for WDString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_wdstr(), f)
+    }
+}
+
+impl Deref for WDString {
+    type Target = WDStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_wdstr()
+    }
+}
+
+impl From<&WDStr> for WDString {
+    #[inline]
+    fn from(value: &WDStr) -> Self {
+        // SAFETY: The slice is copied from a valid `WDStr`.
+        unsafe { WDString::from_vec_with_nul_unchecked(value.as_slice_with_nul().to_vec()) }
+    }
+}
+
+impl From<&DStr> for WDString {
+    #[inline]
+    fn from(value: &DStr) -> Self {
+        let mut string = WDString::with_capacity(value.len());
+        string.push_dstr(value);
+        string
+    }
+}